@@ -1,20 +1,16 @@
 #![allow(clippy::uninlined_format_args)]
 
-use movable_ref::{selfref_accessors, SelfRefCell};
+use movable_ref::self_referential;
 
-struct Node {
-    value: SelfRefCell<String, i16>,
-}
-
-impl Node {
-    fn new(value: String) -> Self {
-        let value = SelfRefCell::new(value).unwrap();
-        Self { value }
+self_referential! {
+    struct Node {
+        text: String,
+    }
+    refs {
+        value: SelfRef<String, i16> { get: get_value } = text,
     }
 }
 
-selfref_accessors!(impl Node { get_value, get_value_mut: value -> String });
-
 impl Node {
     fn len(&self) -> usize {
         self.get_value().len()
@@ -22,13 +18,13 @@ impl Node {
 }
 
 fn main() {
-    let node = Node::new("Hello, World!".to_string());
+    let node = Node::new("Hello, World!".to_string()).unwrap();
     println!("Original: {}", node.get_value());
 
     let boxed_node = Box::new(node);
     println!("In Box: {}", boxed_node.get_value());
 
-    let nodes = [*boxed_node, Node::new("Another node".to_string())];
+    let nodes = [*boxed_node, Node::new("Another node".to_string()).unwrap()];
 
     nodes.iter().enumerate().for_each(|(i, node)| {
         println!("Node {}: '{}' (len: {})", i, node.get_value(), node.len());
@@ -1,32 +1,29 @@
 #![allow(clippy::uninlined_format_args)]
 
-use movable_ref::{selfref_accessors, SelfRef, SelfRefCell};
+use movable_ref::{self_referential, SelfRef};
 use std::hint::black_box;
 use std::time::Instant;
 
-struct SelfRefRelPtr {
-    data: [u64; 100],
-    ptr: SelfRefCell<u64, i16>,
+self_referential! {
+    struct SelfRefRelPtr {
+        data: [u64; 100],
+    }
+    refs {
+        ptr: SelfRef<u64, i16> { get: get_value } = data[50],
+    }
 }
 
 impl SelfRefRelPtr {
-    fn new() -> Self {
-        let mut this = Self {
-            data: [0u64; 100],
-            ptr: SelfRefCell::new(0u64).unwrap(),
-        };
-
-        this.data.iter_mut().enumerate().for_each(|(i, item)| {
+    fn build() -> Self {
+        let mut data = [0u64; 100];
+        data.iter_mut().enumerate().for_each(|(i, item)| {
             *item = i as u64 * 2;
         });
 
-        this.ptr = SelfRefCell::new(this.data[50]).unwrap();
-        this
+        Self::new(data).unwrap()
     }
 }
 
-selfref_accessors!(impl SelfRefRelPtr { get_value : ptr -> u64 });
-
 struct DirectAccess {
     data: [u64; 100],
     index: usize,
@@ -90,7 +87,7 @@ fn benchmark_memory_usage() {
 fn benchmark_access_performance() {
     const ITERATIONS: usize = 10_000_000;
 
-    let rel_ptr_struct = SelfRefRelPtr::new();
+    let rel_ptr_struct = SelfRefRelPtr::build();
     let direct_struct = DirectAccess::new();
 
     let start = Instant::now();
@@ -118,7 +115,7 @@ fn benchmark_access_performance() {
 fn demonstrate_movability() {
     println!("Movability Demonstration:");
 
-    let s = SelfRefRelPtr::new();
+    let s = SelfRefRelPtr::build();
     println!("  Original value: {}", s.get_value());
 
     let boxed = Box::new(s);
@@ -127,7 +124,7 @@ fn demonstrate_movability() {
     let moved_again = *boxed;
     println!("  After unbox:    {}", moved_again.get_value());
 
-    let vec = [moved_again, SelfRefRelPtr::new()];
+    let vec = [moved_again, SelfRefRelPtr::build()];
     println!("  In vector[0]:   {}", vec[0].get_value());
     println!("  In vector[1]:   {}", vec[1].get_value());
 
@@ -0,0 +1,307 @@
+use crate::{IntegerOffset, PointerRecomposition, SelfRef};
+use core::mem::size_of;
+
+/// Compile-time builder for the byte pool of a [`ConstTable`].
+///
+/// Strings and byte strings are appended in order; the pool remembers where each of its
+/// `K` entries starts so that links into it can be computed in the same constant.
+///
+/// ```rust
+/// use movable_ref::ConstPool;
+///
+/// const NAMES: ConstPool<8, 2> = ConstPool::new().push_str("idle").push_str("run");
+/// assert_eq!(NAMES.entry(1), (4, 3));
+/// assert_eq!(NAMES.len(), 7);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConstPool<const M: usize, const K: usize> {
+    bytes: [u8; M],
+    entries: [(usize, usize); K],
+    len: usize,
+    count: usize,
+}
+
+impl<const M: usize, const K: usize> ConstPool<M, K> {
+    /// Creates an empty pool.
+    ///
+    /// # Returns
+    /// * `ConstPool<M, K>` - Pool with room for `M` bytes in `K` entries.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; M],
+            entries: [(0, 0); K],
+            len: 0,
+            count: 0,
+        }
+    }
+
+    /// Appends a byte string as the next entry.
+    ///
+    /// # Parameters
+    /// * `bytes` - Contents of the entry.
+    ///
+    /// # Returns
+    /// * `ConstPool<M, K>` - The pool with the entry added.
+    ///
+    /// # Panics
+    ///
+    /// Panics, or fails to compile in a constant, when the pool runs out of bytes or entries.
+    pub const fn push(mut self, bytes: &[u8]) -> Self {
+        assert!(self.count < K, "pool has no entries left");
+        assert!(bytes.len() <= M - self.len, "pool has no bytes left");
+        let mut i = 0;
+        while i < bytes.len() {
+            self.bytes[self.len + i] = bytes[i];
+            i += 1;
+        }
+        self.entries[self.count] = (self.len, bytes.len());
+        self.len += bytes.len();
+        self.count += 1;
+        self
+    }
+
+    /// Appends a string as the next entry.
+    ///
+    /// # Parameters
+    /// * `text` - Contents of the entry.
+    ///
+    /// # Returns
+    /// * `ConstPool<M, K>` - The pool with the entry added.
+    pub const fn push_str(self, text: &str) -> Self {
+        self.push(text.as_bytes())
+    }
+
+    /// Start and length of an entry.
+    ///
+    /// # Parameters
+    /// * `index` - Position of the entry in push order.
+    ///
+    /// # Returns
+    /// * `(usize, usize)` - Byte offset of the entry in the pool and its length.
+    pub const fn entry(&self, index: usize) -> (usize, usize) {
+        assert!(index < self.count, "pool entry out of range");
+        self.entries[index]
+    }
+
+    /// Number of bytes in use.
+    ///
+    /// # Returns
+    /// * `usize` - Bytes appended so far; the rest of the pool is zeroed.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing has been appended yet.
+    ///
+    /// # Returns
+    /// * `bool` - `true` for an empty pool.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The pool's bytes.
+    ///
+    /// # Returns
+    /// * `[u8; M]` - Contents, ready to be placed in a [`ConstTable`].
+    pub const fn into_bytes(self) -> [u8; M] {
+        self.bytes
+    }
+}
+
+impl<const M: usize, const K: usize> Default for ConstPool<M, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A table of records followed by a byte pool, linked with `SelfRef`s at compile time.
+///
+/// Lookup tables kept in flash or ROM cannot hold absolute pointers without relocation.
+/// `ConstTable` fixes the layout of `N` records of type `R` and an `M`-byte pool, and
+/// [`ConstTable::record_position`] and [`ConstTable::pool_position`] give the position of
+/// each part as a constant. Feeding those positions to
+/// [`SelfRef::from_field_offsets`] builds links between records, and from records into
+/// the pool, inside a `static` initialiser, so the finished table is position-independent.
+///
+/// Links are resolved with [`ConstTable::record`], [`ConstTable::bytes`] and
+/// [`ConstTable::text`], which check that the link and its target both lie inside the
+/// table.
+///
+/// ```rust
+/// use core::mem::offset_of;
+/// use movable_ref::{ConstPool, ConstTable, PointerRecomposition, Ptr, SelfRef};
+///
+/// struct State {
+///     next: SelfRef<State, i16>,
+///     name: SelfRef<str, i16>,
+/// }
+///
+/// // Records are sized, so a link to one carries no metadata.
+/// unsafe impl PointerRecomposition for State {
+///     type Components = ();
+///
+///     fn decompose(_: &Self) {}
+///
+///     unsafe fn recompose(ptr: Ptr<u8>, (): ()) -> Ptr<Self> {
+///         ptr.map(core::ptr::NonNull::cast)
+///     }
+/// }
+///
+/// const NAMES: ConstPool<11, 3> =
+///     ConstPool::new().push_str("idle").push_str("run").push_str("stop");
+///
+/// type Machine = ConstTable<State, 3, 11>;
+///
+/// const fn state(index: usize, next: usize) -> State {
+///     let at = Machine::record_position(index);
+///     let (start, len) = NAMES.entry(index);
+///     State {
+///         next: SelfRef::<State, i16>::from_field_offsets(
+///             at + offset_of!(State, next),
+///             Machine::record_position(next),
+///             (),
+///         ),
+///         name: SelfRef::<str, i16>::from_field_offsets(
+///             at + offset_of!(State, name),
+///             Machine::pool_position(start),
+///             len,
+///         ),
+///     }
+/// }
+///
+/// static MACHINE: Machine =
+///     ConstTable::new([state(0, 1), state(1, 2), state(2, 0)], NAMES.into_bytes());
+///
+/// let idle = &MACHINE.records()[0];
+/// let run = MACHINE.record(&idle.next).unwrap();
+/// assert_eq!(MACHINE.text(&run.name), Some("run"));
+/// ```
+#[repr(C)]
+pub struct ConstTable<R, const N: usize, const M: usize> {
+    records: [R; N],
+    pool: [u8; M],
+}
+
+impl<R, const N: usize, const M: usize> ConstTable<R, N, M> {
+    /// Creates a table from its records and pool.
+    ///
+    /// # Parameters
+    /// * `records` - Records, with links built from this table's positions.
+    /// * `pool` - Bytes referenced by the records.
+    ///
+    /// # Returns
+    /// * `ConstTable<R, N, M>` - The assembled table.
+    pub const fn new(records: [R; N], pool: [u8; M]) -> Self {
+        Self { records, pool }
+    }
+
+    /// Position of a record from the start of the table.
+    ///
+    /// # Parameters
+    /// * `index` - Index of the record.
+    ///
+    /// # Returns
+    /// * `usize` - Byte offset of the record.
+    pub const fn record_position(index: usize) -> usize {
+        assert!(index < N, "record index out of range");
+        index * size_of::<R>()
+    }
+
+    /// Position of a pool byte from the start of the table.
+    ///
+    /// # Parameters
+    /// * `byte` - Offset inside the pool; `M` names the end of the pool.
+    ///
+    /// # Returns
+    /// * `usize` - Byte offset from the start of the table.
+    pub const fn pool_position(byte: usize) -> usize {
+        assert!(byte <= M, "pool offset out of range");
+        // The pool is a byte array, so `#[repr(C)]` adds no padding before it.
+        size_of::<[R; N]>() + byte
+    }
+
+    /// The table's records.
+    ///
+    /// # Returns
+    /// * `&[R; N]` - Shared reference to the records.
+    pub const fn records(&self) -> &[R; N] {
+        &self.records
+    }
+
+    /// The table's byte pool.
+    ///
+    /// # Returns
+    /// * `&[u8; M]` - Shared reference to the pool.
+    pub const fn pool(&self) -> &[u8; M] {
+        &self.pool
+    }
+
+    /// Position of a link's target, provided the link itself lies inside the table.
+    fn target_of<T, I>(&self, link: &SelfRef<T, I>) -> Option<(usize, T::Components)>
+    where
+        T: ?Sized + PointerRecomposition,
+        I: IntegerOffset,
+    {
+        let base = self as *const Self as usize;
+        let at = (link as *const SelfRef<T, I> as usize).checked_sub(base)?;
+        if at + size_of::<SelfRef<T, I>>() > size_of::<Self>() {
+            return None;
+        }
+        let (offset, components) = link.parts_if_ready()?;
+        let target = (at as isize).checked_add(offset.try_into_isize().ok()?)?;
+        usize::try_from(target)
+            .ok()
+            .map(|target| (target, components))
+    }
+
+    /// Resolves a link to one of the table's records.
+    ///
+    /// # Parameters
+    /// * `link` - Pointer stored inside this table.
+    ///
+    /// # Returns
+    /// * `Option<&R>` - The record, or `None` if the link is unset, lies outside the
+    ///   table, or does not point at the start of a record.
+    pub fn record<I: IntegerOffset>(&self, link: &SelfRef<R, I>) -> Option<&R>
+    where
+        R: PointerRecomposition,
+    {
+        // Records are sized, so their position alone identifies them.
+        let (target, _) = self.target_of(link)?;
+        match size_of::<R>() {
+            // Zero-sized records all share the table's first address.
+            0 => self.records.first().filter(|_| target == 0),
+            size if target % size == 0 => self.records.get(target / size),
+            _ => None,
+        }
+    }
+
+    /// Resolves a link to bytes in the table's pool.
+    ///
+    /// # Parameters
+    /// * `link` - Pointer stored inside this table.
+    ///
+    /// # Returns
+    /// * `Option<&[u8]>` - The bytes, or `None` if the link is unset, lies outside the
+    ///   table, or does not point into the pool.
+    pub fn bytes<I: IntegerOffset>(&self, link: &SelfRef<[u8], I>) -> Option<&[u8]> {
+        let (target, len) = self.target_of(link)?;
+        let start = target.checked_sub(size_of::<[R; N]>())?;
+        self.pool.get(start..start.checked_add(len)?)
+    }
+
+    /// Resolves a link to a string in the table's pool.
+    ///
+    /// # Parameters
+    /// * `link` - Pointer stored inside this table.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The string, or `None` if the link is unset, lies outside the
+    ///   table, does not point into the pool, or covers invalid UTF-8.
+    pub fn text<I: IntegerOffset>(&self, link: &SelfRef<str, I>) -> Option<&str> {
+        let (target, len) = self.target_of(link)?;
+        let start = target.checked_sub(size_of::<[R; N]>())?;
+        let bytes = self.pool.get(start..start.checked_add(len)?)?;
+        core::str::from_utf8(bytes).ok()
+    }
+}
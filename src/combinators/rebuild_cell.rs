@@ -0,0 +1,245 @@
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+/// Describes a value that borrows from an owner, as a type constructor over the borrow's
+/// lifetime.
+///
+/// Implemented on a marker type; `Dependent<'a>` names the borrowing type.
+///
+/// ```rust
+/// use movable_ref::DependentFamily;
+///
+/// struct Words;
+///
+/// unsafe impl DependentFamily for Words {
+///     type Dependent<'a> = Vec<&'a [u8]>;
+/// }
+/// ```
+///
+/// # Safety
+///
+/// [`RebuildCell`] drops a stale dependent after its owner has moved, while its borrows
+/// dangle. Dropping `Dependent<'a>` must therefore never read through those borrows: plain
+/// references and containers of references are fine, a `Drop` impl that inspects the
+/// borrowed data is not.
+pub unsafe trait DependentFamily {
+    /// The value borrowing from the owner for `'a`.
+    type Dependent<'a>;
+}
+
+/// Builds the dependent of a [`RebuildCell`] from its owner.
+///
+/// Implemented for every `Fn(&O) -> F::Dependent<'_>`, so closures capturing their
+/// environment can be stored in a cell, and for [`BuildFn`], the plain function the cell
+/// stores by default.
+pub trait Rebuild<O, F: DependentFamily> {
+    /// Builds the dependent borrowing from `owner`.
+    ///
+    /// # Parameters
+    /// * `owner` - Value the dependent borrows from.
+    ///
+    /// # Returns
+    /// * `F::Dependent<'a>` - The freshly built dependent.
+    fn rebuild<'a>(&self, owner: &'a O) -> F::Dependent<'a>;
+}
+
+impl<O, F: DependentFamily, C> Rebuild<O, F> for C
+where
+    C: for<'a> Fn(&'a O) -> F::Dependent<'a>,
+{
+    #[inline]
+    fn rebuild<'a>(&self, owner: &'a O) -> F::Dependent<'a> {
+        self(owner)
+    }
+}
+
+/// A plain function building a dependent, the default builder of a [`RebuildCell`].
+pub struct BuildFn<O, F: DependentFamily>(for<'a> fn(&'a O) -> F::Dependent<'a>);
+
+impl<O, F: DependentFamily> Rebuild<O, F> for BuildFn<O, F> {
+    #[inline]
+    fn rebuild<'a>(&self, owner: &'a O) -> F::Dependent<'a> {
+        (self.0)(owner)
+    }
+}
+
+/// Container that owns a value together with a dependent value borrowing from it.
+///
+/// Many types hold absolute pointers into the data they were built from (a parsed
+/// document referencing its input, for example) and cannot be expressed with `SelfRef`.
+/// `RebuildCell` keeps such a dependent next to its owner and remembers the owner's address
+/// when the dependent was built, the same way `debug-guards` tracks a pointer's target.
+/// When the cell notices that the owner has moved, it rebuilds the dependent from the
+/// owner with the stored builder before handing it out. [`RebuildCell::new`] stores a plain
+/// function; [`RebuildCell::with_builder`] stores a closure, which may capture its
+/// environment.
+///
+/// The dependent is built lazily on first access and is only reachable through closures,
+/// so no borrow of it can outlive the cell.
+///
+/// ```rust
+/// use movable_ref::{DependentFamily, RebuildCell};
+///
+/// struct Words;
+///
+/// unsafe impl DependentFamily for Words {
+///     type Dependent<'a> = Vec<&'a [u8]>;
+/// }
+///
+/// let cell: RebuildCell<[u8; 11], Words> =
+///     RebuildCell::new(*b"hello world", |text| text.split(|b| *b == b' ').collect());
+/// assert_eq!(cell.with(|words| words.len()), 2);
+///
+/// let cell = Box::new(cell);
+/// assert_eq!(cell.with(|words| words[1].as_ptr()), cell.owner()[6..].as_ptr());
+///
+/// let separator = b'o';
+/// let cell = RebuildCell::<_, Words, _>::with_builder(*b"hello world", move |text| {
+///     text.split(|b| *b == separator).collect()
+/// });
+/// assert_eq!(cell.with(|words| words.len()), 3);
+/// ```
+pub struct RebuildCell<O, F: DependentFamily, B = BuildFn<O, F>> {
+    // Declared first so that the dependent is dropped before the owner it borrows from.
+    dependent: UnsafeCell<Option<F::Dependent<'static>>>,
+    addr: Cell<usize>,
+    build: B,
+    owner: O,
+}
+
+impl<O, F: DependentFamily> RebuildCell<O, F> {
+    /// Creates a new cell; the dependent is built on first access.
+    ///
+    /// # Parameters
+    /// * `owner` - Value to be owned by the cell.
+    /// * `build` - Function building the dependent from the owner.
+    ///
+    /// # Returns
+    /// * `RebuildCell<O, F>` - Cell holding the owner.
+    pub fn new(owner: O, build: for<'a> fn(&'a O) -> F::Dependent<'a>) -> Self {
+        Self {
+            dependent: UnsafeCell::new(None),
+            addr: Cell::new(0),
+            build: BuildFn(build),
+            owner,
+        }
+    }
+}
+
+impl<O, F: DependentFamily, B> RebuildCell<O, F, B>
+where
+    B: for<'a> Fn(&'a O) -> F::Dependent<'a>,
+{
+    /// Creates a new cell with a closure as its builder; the dependent is built on first access.
+    ///
+    /// The builder is kept in the cell and called again whenever the dependent is rebuilt.
+    /// For a closure, name the dependent family on the call, as in
+    /// `RebuildCell::<_, Words, _>::with_builder(..)`, so the closure's return type is known.
+    ///
+    /// # Parameters
+    /// * `owner` - Value to be owned by the cell.
+    /// * `build` - Closure building the dependent from the owner.
+    ///
+    /// # Returns
+    /// * `RebuildCell<O, F, B>` - Cell holding the owner.
+    pub fn with_builder(owner: O, build: B) -> Self {
+        Self {
+            dependent: UnsafeCell::new(None),
+            addr: Cell::new(0),
+            build,
+            owner,
+        }
+    }
+}
+
+impl<O, F: DependentFamily, B: Rebuild<O, F>> RebuildCell<O, F, B> {
+    /// Returns the dependent, rebuilding it if the owner moved since it was built.
+    fn current(&self) -> *mut F::Dependent<'_> {
+        let addr = &self.owner as *const O as usize;
+        let slot = self.dependent.get();
+        unsafe {
+            if self.addr.get() != addr || (*slot).is_none() {
+                // No borrow of the old dependent can be alive here: it would pin the cell,
+                // and the cell must have been moved for the address to change.
+                let fresh = ManuallyDrop::new(self.build.rebuild(&self.owner));
+                let fresh = ptr::read(&*fresh as *const F::Dependent<'_> as *const _);
+                *slot = Some(fresh);
+                self.addr.set(addr);
+            }
+            match &mut *slot {
+                Some(dependent) => dependent as *mut F::Dependent<'static> as *mut _,
+                None => unreachable!("dependent was just built"),
+            }
+        }
+    }
+
+    /// Runs `f` with shared access to the dependent.
+    ///
+    /// # Parameters
+    /// * `f` - Closure receiving the dependent.
+    ///
+    /// # Returns
+    /// * `R` - Whatever `f` returns.
+    pub fn with<R>(&self, f: impl for<'a> FnOnce(&'a F::Dependent<'a>) -> R) -> R {
+        f(unsafe { &*self.current() })
+    }
+
+    /// Runs `f` with exclusive access to the dependent.
+    ///
+    /// # Parameters
+    /// * `f` - Closure receiving the dependent.
+    ///
+    /// # Returns
+    /// * `R` - Whatever `f` returns.
+    pub fn with_mut<R>(&mut self, f: impl for<'a> FnOnce(&'a mut F::Dependent<'a>) -> R) -> R {
+        f(unsafe { &mut *self.current() })
+    }
+
+    /// Immutable access to the owner.
+    ///
+    /// # Returns
+    /// * `&O` - Shared reference to the owned value.
+    #[inline]
+    pub fn owner(&self) -> &O {
+        &self.owner
+    }
+
+    /// Mutable access to the owner.
+    ///
+    /// Drops the dependent first; it is rebuilt on the next access.
+    ///
+    /// # Returns
+    /// * `&mut O` - Exclusive reference to the owned value.
+    pub fn owner_mut(&mut self) -> &mut O {
+        *self.dependent.get_mut() = None;
+        &mut self.owner
+    }
+
+    /// Whether a dependent built for the owner's current address is available.
+    ///
+    /// # Returns
+    /// * `bool` - `true` when the next access will not rebuild.
+    pub fn is_current(&self) -> bool {
+        let built = unsafe { (*self.dependent.get()).is_some() };
+        built && self.addr.get() == &self.owner as *const O as usize
+    }
+
+    /// Consumes the cell and returns the owner, dropping the dependent.
+    ///
+    /// # Returns
+    /// * `O` - The owned value.
+    pub fn into_owner(mut self) -> O {
+        *self.dependent.get_mut() = None;
+        self.owner
+    }
+}
+
+impl<O: fmt::Debug, F: DependentFamily, B> fmt::Debug for RebuildCell<O, F, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RebuildCell")
+            .field("owner", &self.owner)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,10 +1,145 @@
+use crate::error::InitError;
 use crate::offset::Nullable;
 use crate::{Offset, PointerRecomposition, SelfRef};
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of, size_of_val, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 /// Container that provides safe access to a self-referenced value.
-pub struct SelfRefCell<T: PointerRecomposition, I: Offset = isize> {
+///
+/// The cell owns a value of type `T` and exposes a view of type `U` into it. By default the
+/// view is the whole value; [`SelfRefCell::new_with`] records a different one, such as a
+/// slice of an array or a trait object wrapping the value. Because the value is the last
+/// field, `T` may be unsized: a boxed cell coerces to `Box<SelfRefCell<[u8], I, [u8]>>` or
+/// to a trait-object cell, so cells of different concrete types can share one collection.
+///
+/// ```rust
+/// use movable_ref::SelfRefCell;
+///
+/// let cell: SelfRefCell<[u8; 4], i8, [u8]> =
+///     SelfRefCell::new_with([1, 2, 3, 4], |bytes| &mut bytes[1..]).unwrap();
+/// assert_eq!(cell.get(), [2, 3, 4]);
+///
+/// let boxed: Box<SelfRefCell<[u8], i8, [u8]>> = Box::new(cell);
+/// assert_eq!(boxed.get(), [2, 3, 4]);
+/// ```
+///
+/// # Thread Safety
+///
+/// The cell is `Send` when `T` is `Send` and `Sync` when `T` is `Sync`, so it can be handed
+/// to worker threads or shared through `Arc`. Its pointer stores the distance between two
+/// of its own fields, which a move to another thread preserves like any other move. Shared
+/// access only ever yields `&U`; `&mut U` requires `&mut self`.
+///
+/// # Constant Construction
+///
+/// [`SelfRefCell::const_new`] builds a cell in a `const` or `static` item, for tables that
+/// live in read-only memory on embedded targets. The cell is `#[repr(C)]` with its pointer
+/// first, so the distance to the value is known at compile time. Each offset integer has
+/// its own `const_new`, so name it on the call.
+///
+/// ```rust
+/// use movable_ref::SelfRefCell;
+///
+/// static GREETING: SelfRefCell<[u8; 5], i8> = SelfRefCell::<_, i8>::const_new(*b"hello");
+/// assert_eq!(GREETING.get(), b"hello");
+/// ```
+#[repr(C)]
+pub struct SelfRefCell<T: ?Sized, I: Offset = isize, U: ?Sized + PointerRecomposition = T> {
+    ptr: SelfRef<U, I>,
     value: T,
-    ptr: SelfRef<T, I>,
+}
+
+/// The uninitialised value slot handed to the in-place [`SelfRefCell`] builders.
+///
+/// The only safe way to finish a slot is [`CellSlot::write`], which returns the
+/// [`FilledSlot`] the builder expects back. Both carry the same invariant lifetime, so a
+/// builder can only be handed the proof for the slot it gave out:
+///
+/// ```compile_fail
+/// use movable_ref::{CellSlot, FilledSlot, SelfRefCell};
+/// fn forge(_: CellSlot<'_, u32>) -> FilledSlot<'static, u32> {
+///     unimplemented!()
+/// }
+/// let _ = SelfRefCell::<u32, i8>::new_in_place(|slot| forge(slot));
+/// ```
+pub struct CellSlot<'a, T> {
+    slot: &'a mut MaybeUninit<T>,
+    brand: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+/// Proof that a [`CellSlot`] has been filled, giving access to the written value.
+pub struct FilledSlot<'a, T> {
+    value: &'a mut T,
+    brand: PhantomData<fn(&'a ()) -> &'a ()>,
+}
+
+impl<'a, T> CellSlot<'a, T> {
+    /// Writes the value into the slot.
+    ///
+    /// # Parameters
+    /// * `value` - Value to be owned by the cell.
+    ///
+    /// # Returns
+    /// * `FilledSlot<'a, T>` - Proof of initialisation, to be returned to the builder.
+    #[inline]
+    pub fn write(self, value: T) -> FilledSlot<'a, T> {
+        FilledSlot {
+            value: self.slot.write(value),
+            brand: PhantomData,
+        }
+    }
+
+    /// Raw pointer to the slot, for initialising the value field by field.
+    ///
+    /// # Returns
+    /// * `*mut T` - Pointer valid for writes of `T`.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.slot.as_mut_ptr()
+    }
+
+    /// Declares the slot filled after writing it through [`CellSlot::as_mut_ptr`].
+    ///
+    /// # Safety
+    ///
+    /// The slot must hold a fully initialised `T`.
+    ///
+    /// # Returns
+    /// * `FilledSlot<'a, T>` - Proof of initialisation, to be returned to the builder.
+    #[inline]
+    pub unsafe fn assume_init(self) -> FilledSlot<'a, T> {
+        FilledSlot {
+            value: self.slot.assume_init_mut(),
+            brand: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for FilledSlot<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for FilledSlot<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
 }
 
 impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefCell<T, I> {
@@ -18,18 +153,291 @@ impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefCell<T, I> {
     ///   cannot encode the distance.
     pub fn new(value: T) -> Result<Self, I::Error> {
         let mut this = Self {
-            value,
             ptr: SelfRef::null(),
+            value,
         };
         this.ptr.set(&mut this.value)?;
         Ok(this)
     }
 
+    /// Initialises a cell in the uninitialised memory at `this`.
+    ///
+    /// The distance is validated before `init` runs, so a failure never leaves a value
+    /// behind that would need dropping. If `init` fails or panics, `this` stays
+    /// uninitialised; if it returns, the slot's brand guarantees the value was written.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for writes and properly aligned for `Self`.
+    unsafe fn init_at<E, F>(this: *mut Self, init: F) -> Result<(), InitError<E, I::Error>>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> Result<FilledSlot<'a, T>, E>,
+    {
+        let value = ptr::addr_of_mut!((*this).value);
+        let pointer = ptr::addr_of_mut!((*this).ptr);
+        I::sub(value as *mut u8, pointer as *mut u8).map_err(InitError::Offset)?;
+
+        let slot = CellSlot {
+            slot: &mut *(value as *mut MaybeUninit<T>),
+            brand: PhantomData,
+        };
+        init(slot).map_err(InitError::Init)?;
+
+        pointer.write(SelfRef::null());
+        (*pointer).set_unchecked(value);
+        Ok(())
+    }
+
+    /// Creates a cell by initialising the value directly in its slot inside the cell.
+    ///
+    /// The initialiser fills the slot, typically with `slot.write(..)`, and returns the
+    /// resulting [`FilledSlot`]; the pointer is wired up before the cell is returned. The
+    /// cell itself is still built on the stack and returned by value, so a large `T` passes
+    /// through the stack either way. Use [`SelfRefCell::new_boxed_in_place`] or
+    /// [`SelfRefCell::push_in_place`] together with [`CellSlot::as_mut_ptr`] to build it
+    /// directly in its final heap location instead.
+    ///
+    /// ```rust
+    /// use movable_ref::SelfRefCell;
+    /// let cell: SelfRefCell<[u64; 64], i16> =
+    ///     SelfRefCell::new_in_place(|slot| slot.write([7; 64])).unwrap();
+    /// assert_eq!(cell.get()[63], 7);
+    /// ```
+    ///
+    /// A panic inside `init` leaves nothing to drop.
+    ///
+    /// # Parameters
+    /// * `init` - Closure writing the value into the slot.
+    ///
+    /// # Returns
+    /// * `Result<Self, I::Error>` - `Ok` with an initialised cell, or the offset error when `I`
+    ///   cannot encode the distance.
+    pub fn new_in_place<F>(init: F) -> Result<Self, I::Error>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> FilledSlot<'a, T>,
+    {
+        Self::try_new_with(|slot| Ok::<_, Infallible>(init(slot))).map_err(InitError::into_offset)
+    }
+
+    /// Creates a cell with a fallible in-place initialiser.
+    ///
+    /// # Parameters
+    /// * `init` - Closure writing the value into the slot, or reporting why it could not.
+    ///
+    /// # Returns
+    /// * `Result<Self, InitError<E, I::Error>>` - `Ok` with an initialised cell, or the offset
+    ///   or initialiser error.
+    pub fn try_new_with<E, F>(init: F) -> Result<Self, InitError<E, I::Error>>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> Result<FilledSlot<'a, T>, E>,
+    {
+        let mut this = MaybeUninit::<Self>::uninit();
+        unsafe {
+            Self::init_at(this.as_mut_ptr(), init)?;
+            Ok(this.assume_init())
+        }
+    }
+
+    /// Creates a boxed cell, initialising the value directly on the heap.
+    ///
+    /// # Returns
+    /// * `Result<Box<Self>, I::Error>` - `Ok` with an initialised cell, or the offset error.
+    pub fn new_boxed_in_place<F>(init: F) -> Result<Box<Self>, I::Error>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> FilledSlot<'a, T>,
+    {
+        Self::try_new_boxed_with(|slot| Ok::<_, Infallible>(init(slot)))
+            .map_err(InitError::into_offset)
+    }
+
+    /// Creates a boxed cell with a fallible initialiser running directly on the heap.
+    ///
+    /// The allocation is released if `init` fails or panics.
+    ///
+    /// # Returns
+    /// * `Result<Box<Self>, InitError<E, I::Error>>` - `Ok` with an initialised cell, or the
+    ///   offset or initialiser error.
+    pub fn try_new_boxed_with<E, F>(init: F) -> Result<Box<Self>, InitError<E, I::Error>>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> Result<FilledSlot<'a, T>, E>,
+    {
+        let mut boxed = Box::new(MaybeUninit::<Self>::uninit());
+        unsafe {
+            Self::init_at(boxed.as_mut_ptr(), init)?;
+            Ok(Box::from_raw(Box::into_raw(boxed).cast::<Self>()))
+        }
+    }
+
+    /// Appends a cell to `vec`, initialising the value directly in the vector's buffer.
+    ///
+    /// The vector is unchanged if `init` panics.
+    ///
+    /// # Returns
+    /// * `Result<&mut Self, I::Error>` - The new element, or the offset error.
+    pub fn push_in_place<F>(vec: &mut Vec<Self>, init: F) -> Result<&mut Self, I::Error>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> FilledSlot<'a, T>,
+    {
+        Self::try_push_with(vec, |slot| Ok::<_, Infallible>(init(slot)))
+            .map_err(InitError::into_offset)
+    }
+
+    /// Appends a cell to `vec` with a fallible initialiser running in the vector's buffer.
+    ///
+    /// The vector is unchanged if `init` fails or panics.
+    ///
+    /// # Returns
+    /// * `Result<&mut Self, InitError<E, I::Error>>` - The new element, or the offset or
+    ///   initialiser error.
+    pub fn try_push_with<E, F>(
+        vec: &mut Vec<Self>,
+        init: F,
+    ) -> Result<&mut Self, InitError<E, I::Error>>
+    where
+        F: for<'a> FnOnce(CellSlot<'a, T>) -> Result<FilledSlot<'a, T>, E>,
+    {
+        vec.reserve(1);
+        let len = vec.len();
+        unsafe {
+            Self::init_at(vec.as_mut_ptr().add(len), init)?;
+            vec.set_len(len + 1);
+        }
+        Ok(&mut vec[len])
+    }
+
+    /// Points the pointer at the value again after it was mutated in place.
+    #[inline]
+    fn reseal(&mut self) {
+        unsafe { self.ptr.set_unchecked(&mut self.value) }
+    }
+
+    /// Runs `f` with exclusive access to the value, then revalidates the pointer.
+    ///
+    /// ```rust
+    /// use movable_ref::SelfRefCell;
+    /// let mut cell: SelfRefCell<Option<u32>, i8> = SelfRefCell::new(None).unwrap();
+    /// let previous = cell.with_mut(|value| value.replace(5));
+    /// assert_eq!((previous, *cell.get()), (None, Some(5)));
+    /// ```
+    ///
+    /// # Parameters
+    /// * `f` - Closure receiving the value.
+    ///
+    /// # Returns
+    /// * `R` - Whatever `f` returns.
+    pub fn with_mut<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = f(self.get_mut());
+        self.reseal();
+        result
+    }
+
+    /// Replaces the value, re-pointing the cell at the new one.
+    ///
+    /// # Parameters
+    /// * `value` - New value to store.
+    ///
+    /// # Returns
+    /// * `T` - The previous value.
+    pub fn replace(&mut self, value: T) -> T {
+        let previous = core::mem::replace(&mut self.value, value);
+        self.reseal();
+        previous
+    }
+
+    /// Takes the value, leaving `T::default()` in its place.
+    ///
+    /// # Returns
+    /// * `T` - The previous value.
+    pub fn take(&mut self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+
+    /// Swaps the values of two cells, re-pointing both.
+    ///
+    /// # Parameters
+    /// * `other` - Cell to exchange values with.
+    pub fn swap(&mut self, other: &mut Self) {
+        core::mem::swap(&mut self.value, &mut other.value);
+        self.reseal();
+        other.reseal();
+    }
+
+    /// Consumes the cell and builds a new one from a transformed value.
+    ///
+    /// # Parameters
+    /// * `f` - Closure mapping the value.
+    ///
+    /// # Returns
+    /// * `Result<SelfRefCell<V, I>, I::Error>` - `Ok` with the new cell, or the offset error
+    ///   when `I` cannot encode the distance for `V`.
+    pub fn map_into<V, F>(self, f: F) -> Result<SelfRefCell<V, I>, I::Error>
+    where
+        V: PointerRecomposition,
+        F: FnOnce(T) -> V,
+    {
+        SelfRefCell::new(f(self.value))
+    }
+}
+
+impl<T, I: Offset + Nullable, U: ?Sized + PointerRecomposition> SelfRefCell<T, I, U> {
+    /// Creates a cell whose pointer targets a projection of the value.
+    ///
+    /// `project` receives the value in its final slot and returns the view the cell should
+    /// expose, for example a sub-slice or a trait object wrapping the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the returned view does not lie inside the value; a view into memory the
+    /// value merely owns (such as a `Vec`'s heap buffer) would not move with the cell.
+    ///
+    /// # Parameters
+    /// * `value` - Value to be owned by the cell.
+    /// * `project` - Closure selecting the view to reference.
+    ///
+    /// # Returns
+    /// * `Result<Self, I::Error>` - `Ok` with an initialised cell, or the offset error when `I`
+    ///   cannot encode the distance.
+    pub fn new_with<F>(value: T, project: F) -> Result<Self, I::Error>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let mut this = Self {
+            ptr: SelfRef::null(),
+            value,
+        };
+        let start = &this.value as *const T as usize;
+        let end = start + size_of::<T>();
+        let view = project(&mut this.value);
+        let addr = view as *mut U as *mut u8 as usize;
+        assert!(
+            start <= addr && addr + size_of_val(view) <= end,
+            "SelfRefCell projection must stay inside the value"
+        );
+        this.ptr.set(view)?;
+        Ok(this)
+    }
+
+    /// Consumes the cell and returns the value.
+    ///
+    /// # Returns
+    /// * `T` - The owned value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition> SelfRefCell<T, I, U> {
     /// Immutable access to the value.
     ///
     /// # Returns
-    /// * `&T` - Shared reference to the stored value.
-    pub fn get(&self) -> &T {
+    /// * `&U` - Shared reference to the stored value.
+    pub fn get(&self) -> &U {
         self.try_get()
             .expect("SelfRefCell accessed before initialisation")
     }
@@ -37,9 +445,9 @@ impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefCell<T, I> {
     /// Immutable access to the value if the pointer has been initialised.
     ///
     /// # Returns
-    /// * `Option<&T>` - Shared reference when the pointer is ready.
+    /// * `Option<&U>` - Shared reference when the pointer is ready.
     #[inline]
-    pub fn try_get(&self) -> Option<&T> {
+    pub fn try_get(&self) -> Option<&U> {
         if !self.ptr.is_ready() {
             return None;
         }
@@ -47,11 +455,26 @@ impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefCell<T, I> {
         Some(unsafe { self.ptr.get_ref_from_base_unchecked(base) })
     }
 
+    /// Runs `f` with shared access to the value.
+    ///
+    /// # Parameters
+    /// * `f` - Closure receiving the value.
+    ///
+    /// # Returns
+    /// * `R` - Whatever `f` returns.
+    #[inline]
+    pub fn with<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&U) -> R,
+    {
+        f(self.get())
+    }
+
     /// Mutable access to the value.
     ///
     /// # Returns
-    /// * `&mut T` - Exclusive reference to the stored value.
-    pub fn get_mut(&mut self) -> &mut T {
+    /// * `&mut U` - Exclusive reference to the stored value.
+    pub fn get_mut(&mut self) -> &mut U {
         self.try_get_mut()
             .expect("SelfRefCell accessed before initialisation")
     }
@@ -59,21 +482,168 @@ impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefCell<T, I> {
     /// Mutable access to the value if the pointer has been initialised.
     ///
     /// # Returns
-    /// * `Option<&mut T>` - Exclusive reference when the pointer is ready.
+    /// * `Option<&mut U>` - Exclusive reference when the pointer is ready.
     #[inline]
-    pub fn try_get_mut(&mut self) -> Option<&mut T> {
+    pub fn try_get_mut(&mut self) -> Option<&mut U> {
         if !self.ptr.is_ready() {
             return None;
         }
         let base = self as *mut _ as *mut u8;
         Some(unsafe { self.ptr.get_mut_from_base_unchecked(base) })
     }
+}
 
-    /// Consumes the cell and returns the value.
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition> Deref
+    for SelfRefCell<T, I, U>
+{
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        self.get()
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition> DerefMut
+    for SelfRefCell<T, I, U>
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut U {
+        self.get_mut()
+    }
+}
+
+impl<T: PointerRecomposition + Clone, I: Offset + Nullable> Clone for SelfRefCell<T, I> {
+    /// Clones the value into a new cell whose pointer targets the clone.
+    fn clone(&self) -> Self {
+        match Self::new(self.get().clone()) {
+            Ok(cell) => cell,
+            Err(_) => unreachable!("a clone has the same layout as its source"),
+        }
+    }
+}
+
+impl<T: PointerRecomposition + Default, I: Offset + Nullable> Default for SelfRefCell<T, I> {
+    /// Creates a cell holding `T::default()`.
     ///
-    /// # Returns
-    /// * `T` - The owned value.
-    pub fn into_inner(self) -> T {
-        self.value
+    /// # Panics
+    ///
+    /// Panics when `I` cannot encode the distance between the cell's fields.
+    fn default() -> Self {
+        match Self::new(T::default()) {
+            Ok(cell) => cell,
+            Err(_) => panic!("SelfRefCell offset does not fit in the offset type"),
+        }
+    }
+}
+
+// A blanket `TryFrom<T>` would overlap with `impl<T, U: Into<T>> TryFrom<U> for T` in core,
+// so the conversion is provided for the types this crate implements `PointerRecomposition`
+// for. Other types go through `SelfRefCell::new`.
+macro_rules! impl_try_from_value {
+    ($([$($generics:tt)*] $type:ty),* $(,)?) => {$(
+        impl<$($generics)* I: Offset + Nullable> TryFrom<$type> for SelfRefCell<$type, I> {
+            type Error = I::Error;
+
+            #[inline]
+            fn try_from(value: $type) -> Result<Self, I::Error> {
+                Self::new(value)
+            }
+        }
+    )*};
+}
+
+impl_try_from_value! {
+    [] u8, [] u16, [] u32, [] u64, [] u128, [] usize,
+    [] i8, [] i16, [] i32, [] i64, [] i128, [] isize,
+    [] f32, [] f64, [] bool, [] char, [] (), [] String,
+    [T,] Vec<T>, [T,] Option<T>, [T, E,] Result<T, E>,
+    [T, const N: usize,] [T; N],
+    [A,] (A,), [A, B,] (A, B), [A, B, C,] (A, B, C),
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + fmt::Debug> fmt::Debug
+    for SelfRefCell<T, I, U>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get(), f)
     }
 }
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + fmt::Display> fmt::Display
+    for SelfRefCell<T, I, U>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get(), f)
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + PartialEq> PartialEq
+    for SelfRefCell<T, I, U>
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + Eq> Eq
+    for SelfRefCell<T, I, U>
+{
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + PartialOrd> PartialOrd
+    for SelfRefCell<T, I, U>
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.get().partial_cmp(other.get())
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + Ord> Ord
+    for SelfRefCell<T, I, U>
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+impl<T: ?Sized, I: Offset + Nullable, U: ?Sized + PointerRecomposition + Hash> Hash
+    for SelfRefCell<T, I, U>
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+macro_rules! impl_const_new {
+    ($($type:ty),* $(,)?) => {$(
+        impl<T: PointerRecomposition<Components = ()>> SelfRefCell<T, $type> {
+            /// Creates a new cell in a constant context.
+            ///
+            /// # Parameters
+            /// * `value` - Value to be owned by the cell and referenced internally.
+            ///
+            /// # Returns
+            /// * `SelfRefCell<T, I>` - Initialised cell.
+            ///
+            /// # Panics
+            ///
+            /// Panics, or fails to compile in a constant, when `I` cannot reach the value.
+            pub const fn const_new(value: T) -> Self {
+                // `#[repr(C)]` places the value after the pointer, rounded up to its alignment.
+                let align = align_of::<T>();
+                let target = (size_of::<SelfRef<T, $type>>() + align - 1) & !(align - 1);
+                Self {
+                    ptr: SelfRef::<T, $type>::from_field_offsets(0, target, ()),
+                    value,
+                }
+            }
+        }
+    )*};
+}
+
+impl_const_new! { i8, i16, i32, i64, i128, isize }
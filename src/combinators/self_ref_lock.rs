@@ -0,0 +1,210 @@
+use crate::offset::Nullable;
+use crate::sync::{spin_loop, AtomicUsize, Ordering};
+use crate::{Offset, PointerRecomposition, SelfRef};
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+const WRITER: usize = !(usize::MAX >> 1);
+
+/// Thread-safe container that guards a self-referenced value with a reader-writer spin lock.
+///
+/// The `Sync` counterpart of [`SelfRefRefCell`](crate::SelfRefRefCell): any number of readers
+/// or a single writer may access the value through `&self`. The lock spins (yielding to the
+/// scheduler when `std` is available) rather than parking, which keeps it usable in `no_std`
+/// builds and suits short critical sections. Guards resolve the value through the stored
+/// `SelfRef`, starting from the lock's own address.
+///
+/// ```rust
+/// use movable_ref::SelfRefLock;
+/// use std::sync::Arc;
+///
+/// let lock: Arc<SelfRefLock<[u32; 4], i8>> = Arc::new(SelfRefLock::new([0; 4]).unwrap());
+/// let writer = {
+///     let lock = Arc::clone(&lock);
+///     std::thread::spawn(move || lock.write()[0] = 7)
+/// };
+/// writer.join().unwrap();
+/// assert_eq!(lock.read()[0], 7);
+/// ```
+pub struct SelfRefLock<T: PointerRecomposition, I: Offset = isize> {
+    value: UnsafeCell<T>,
+    ptr: SelfRef<T, I>,
+    state: AtomicUsize,
+}
+
+// SAFETY: The lock hands out `&T` to several threads at once and `&mut T` to one thread
+// at a time, which needs `T: Send + Sync` exactly like `RwLock<T>`.
+unsafe impl<T: PointerRecomposition + Send + Sync, I: Offset + Send + Sync> Sync
+    for SelfRefLock<T, I>
+where
+    T::Components: Sync,
+{
+}
+
+/// Shared guard returned by [`SelfRefLock::read`].
+pub struct SelfRefReadGuard<'a, T> {
+    value: &'a T,
+    state: &'a AtomicUsize,
+}
+
+/// Exclusive guard returned by [`SelfRefLock::write`].
+pub struct SelfRefWriteGuard<'a, T> {
+    value: NonNull<T>,
+    state: &'a AtomicUsize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefLock<T, I> {
+    /// Creates a new unlocked container.
+    ///
+    /// # Parameters
+    /// * `value` - Value to be owned by the lock and referenced internally.
+    ///
+    /// # Returns
+    /// * `Result<Self, I::Error>` - `Ok` with an initialised lock, or the offset error when `I`
+    ///   cannot encode the distance.
+    pub fn new(value: T) -> Result<Self, I::Error> {
+        let mut this = Self {
+            value: UnsafeCell::new(value),
+            ptr: SelfRef::null(),
+            state: AtomicUsize::new(0),
+        };
+        this.ptr.set(this.value.get_mut())?;
+        Ok(this)
+    }
+
+    /// Acquires shared access, spinning while a writer holds the lock.
+    ///
+    /// # Returns
+    /// * `SelfRefReadGuard<'_, T>` - Guard releasing the lock when dropped.
+    pub fn read(&self) -> SelfRefReadGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+            spin_loop();
+        }
+    }
+
+    /// Acquires shared access if no writer holds the lock.
+    ///
+    /// # Returns
+    /// * `Option<SelfRefReadGuard<'_, T>>` - Guard when the lock was acquired.
+    pub fn try_read(&self) -> Option<SelfRefReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            if state & WRITER != 0 || state == WRITER - 1 {
+                return None;
+            }
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => state = actual,
+            }
+        }
+        let base = self as *const Self as *const u8;
+        let value = unsafe { self.ptr.get_ref_from_base_unchecked(base) };
+        Some(SelfRefReadGuard {
+            value,
+            state: &self.state,
+        })
+    }
+
+    /// Acquires exclusive access, spinning while the lock is held.
+    ///
+    /// # Returns
+    /// * `SelfRefWriteGuard<'_, T>` - Guard releasing the lock when dropped.
+    pub fn write(&self) -> SelfRefWriteGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_write() {
+                return guard;
+            }
+            spin_loop();
+        }
+    }
+
+    /// Acquires exclusive access if the lock is free.
+    ///
+    /// # Returns
+    /// * `Option<SelfRefWriteGuard<'_, T>>` - Guard when the lock was acquired.
+    pub fn try_write(&self) -> Option<SelfRefWriteGuard<'_, T>> {
+        self.state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        let base = self as *const Self as *mut u8;
+        Some(SelfRefWriteGuard {
+            value: NonNull::from(unsafe { self.ptr.get_mut_from_base_unchecked(base) }),
+            state: &self.state,
+            marker: PhantomData,
+        })
+    }
+
+    /// Mutable access to the value without locking.
+    ///
+    /// # Returns
+    /// * `&mut T` - Exclusive reference to the stored value.
+    pub fn get_mut(&mut self) -> &mut T {
+        let base = self as *mut Self as *mut u8;
+        unsafe { self.ptr.get_mut_from_base_unchecked(base) }
+    }
+
+    /// Consumes the lock and returns the value.
+    ///
+    /// # Returns
+    /// * `T` - The owned value.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: PointerRecomposition + fmt::Debug, I: Offset + Nullable> fmt::Debug for SelfRefLock<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("SelfRefLock");
+        match self.try_read() {
+            Some(value) => d.field("value", &&*value),
+            None => d.field("value", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+impl<T> Deref for SelfRefReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> Drop for SelfRefReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T> Deref for SelfRefWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T> DerefMut for SelfRefWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T> Drop for SelfRefWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.state.store(0, Ordering::Release);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::offset::Nullable;
+use crate::{Offset, PointerRecomposition, SelfRef};
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+const WRITING: isize = -1;
+
+/// Single-threaded container that hands out checked borrows of a self-referenced value.
+///
+/// Where [`SelfRefCell`](crate::SelfRefCell) needs `&mut self` for mutation,
+/// `SelfRefRefCell` tracks borrows at runtime like `RefCell`, so a value held behind `&self`
+/// (for example inside an `Rc`) can still be mutated. Both guards resolve the value through
+/// the stored `SelfRef`, starting from the cell's own address.
+///
+/// ```rust
+/// use movable_ref::SelfRefRefCell;
+/// use std::rc::Rc;
+///
+/// let cell: Rc<SelfRefRefCell<Vec<u8>, i8>> = Rc::new(SelfRefRefCell::new(vec![1]).unwrap());
+/// cell.borrow_mut().push(2);
+/// assert_eq!(*cell.borrow(), [1, 2]);
+/// ```
+pub struct SelfRefRefCell<T: PointerRecomposition, I: Offset = isize> {
+    value: UnsafeCell<T>,
+    ptr: SelfRef<T, I>,
+    borrow: Cell<isize>,
+}
+
+/// Shared borrow guard returned by [`SelfRefRefCell::borrow`].
+pub struct SelfRefBorrow<'a, T> {
+    value: &'a T,
+    borrow: &'a Cell<isize>,
+}
+
+/// Exclusive borrow guard returned by [`SelfRefRefCell::borrow_mut`].
+pub struct SelfRefBorrowMut<'a, T> {
+    value: NonNull<T>,
+    borrow: &'a Cell<isize>,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T: PointerRecomposition, I: Offset + Nullable> SelfRefRefCell<T, I> {
+    /// Creates a new cell.
+    ///
+    /// # Parameters
+    /// * `value` - Value to be owned by the cell and referenced internally.
+    ///
+    /// # Returns
+    /// * `Result<Self, I::Error>` - `Ok` with an initialised cell, or the offset error when `I`
+    ///   cannot encode the distance.
+    pub fn new(value: T) -> Result<Self, I::Error> {
+        let mut this = Self {
+            value: UnsafeCell::new(value),
+            ptr: SelfRef::null(),
+            borrow: Cell::new(0),
+        };
+        this.ptr.set(this.value.get_mut())?;
+        Ok(this)
+    }
+
+    /// Immutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    ///
+    /// # Returns
+    /// * `SelfRefBorrow<'_, T>` - Guard releasing the borrow when dropped.
+    pub fn borrow(&self) -> SelfRefBorrow<'_, T> {
+        self.try_borrow()
+            .expect("SelfRefRefCell already mutably borrowed")
+    }
+
+    /// Immutably borrows the value if it is not mutably borrowed.
+    ///
+    /// # Returns
+    /// * `Option<SelfRefBorrow<'_, T>>` - Guard when the borrow succeeded.
+    pub fn try_borrow(&self) -> Option<SelfRefBorrow<'_, T>> {
+        let count = self.borrow.get();
+        if count == WRITING || count == isize::MAX {
+            return None;
+        }
+        self.borrow.set(count + 1);
+        let base = self as *const Self as *const u8;
+        let value = unsafe { self.ptr.get_ref_from_base_unchecked(base) };
+        Some(SelfRefBorrow {
+            value,
+            borrow: &self.borrow,
+        })
+    }
+
+    /// Mutably borrows the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed.
+    ///
+    /// # Returns
+    /// * `SelfRefBorrowMut<'_, T>` - Guard releasing the borrow when dropped.
+    pub fn borrow_mut(&self) -> SelfRefBorrowMut<'_, T> {
+        self.try_borrow_mut()
+            .expect("SelfRefRefCell already borrowed")
+    }
+
+    /// Mutably borrows the value if it is not borrowed.
+    ///
+    /// # Returns
+    /// * `Option<SelfRefBorrowMut<'_, T>>` - Guard when the borrow succeeded.
+    pub fn try_borrow_mut(&self) -> Option<SelfRefBorrowMut<'_, T>> {
+        if self.borrow.get() != 0 {
+            return None;
+        }
+        self.borrow.set(WRITING);
+        let base = self as *const Self as *mut u8;
+        Some(SelfRefBorrowMut {
+            value: NonNull::from(unsafe { self.ptr.get_mut_from_base_unchecked(base) }),
+            borrow: &self.borrow,
+            marker: PhantomData,
+        })
+    }
+
+    /// Mutable access to the value without borrow tracking.
+    ///
+    /// # Returns
+    /// * `&mut T` - Exclusive reference to the stored value.
+    pub fn get_mut(&mut self) -> &mut T {
+        let base = self as *mut Self as *mut u8;
+        unsafe { self.ptr.get_mut_from_base_unchecked(base) }
+    }
+
+    /// Consumes the cell and returns the value.
+    ///
+    /// # Returns
+    /// * `T` - The owned value.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: PointerRecomposition + fmt::Debug, I: Offset + Nullable> fmt::Debug
+    for SelfRefRefCell<T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("SelfRefRefCell");
+        match self.try_borrow() {
+            Some(value) => d.field("value", &&*value),
+            None => d.field("value", &format_args!("<borrowed>")),
+        };
+        d.finish()
+    }
+}
+
+impl<T> Deref for SelfRefBorrow<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> Drop for SelfRefBorrow<'_, T> {
+    fn drop(&mut self) {
+        self.borrow.set(self.borrow.get() - 1);
+    }
+}
+
+impl<T> Deref for SelfRefBorrowMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T> DerefMut for SelfRefBorrowMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T> Drop for SelfRefBorrowMut<'_, T> {
+    fn drop(&mut self) {
+        self.borrow.set(0);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::offset::Nullable;
+use crate::{Offset, PointerRecomposition, SelfRef};
+use core::mem::{size_of, size_of_val};
+use core::ops::{Deref, DerefMut};
+
+/// Container that owns a value and several relative views into it.
+///
+/// A common layout is one owned buffer with named regions (a header, a body and a trailing
+/// checksum, say). `SelfRefViews` records one `SelfRef` per region at construction using a
+/// selector function, and resolves them by index. Indices are usually given names through
+/// constants. Every view must lie inside the owner, so they all stay valid when the container
+/// moves.
+///
+/// Mutating the owner through [`SelfRefViews::get_mut`] re-runs the selector when the guard is
+/// finished or dropped, so views follow any change in the owner's contents. The views are
+/// replaced all at once: if any new view is rejected, every view is left unset rather than
+/// a mix of old and new ones, and resolving an unset view fails.
+///
+/// ```rust
+/// use movable_ref::SelfRefViews;
+///
+/// const HEADER: usize = 0;
+/// const BODY: usize = 1;
+///
+/// let frame: SelfRefViews<[u8; 8], [u8], 2, i8> =
+///     SelfRefViews::new(*b"HDbodyCK", |bytes| [&bytes[..2], &bytes[2..6]]).unwrap();
+/// let frame = Box::new(frame);
+/// assert_eq!(frame.view(HEADER), b"HD");
+/// assert_eq!(frame.view(BODY), b"body");
+/// ```
+pub struct SelfRefViews<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset = isize> {
+    owner: O,
+    views: [SelfRef<V, I>; N],
+    select: for<'a> fn(&'a O) -> [&'a V; N],
+}
+
+/// Guard returned by [`SelfRefViews::get_mut`] that revalidates every view when finished.
+///
+/// [`SelfRefViewsGuard::finish`] reports a failed revalidation as an error. Dropping the
+/// guard revalidates as well and panics on failure, except while the thread is already
+/// panicking (detected with the `std` feature): then the owner may have been left
+/// half-updated, so the views are unset without running the selector.
+pub struct SelfRefViewsGuard<
+    'a,
+    O,
+    V: ?Sized + PointerRecomposition,
+    const N: usize,
+    I: Offset + Nullable,
+> {
+    views: &'a mut SelfRefViews<O, V, N, I>,
+}
+
+/// Why re-recording the views failed.
+enum Stale<E> {
+    /// A selected view does not lie inside the owner.
+    Outside,
+    /// The distance to a selected view does not fit in the offset type.
+    Offset(E),
+}
+
+impl<E> Stale<E> {
+    /// Reports a view outside the owner as a panic, the documented contract of the selector.
+    fn into_offset(self) -> E {
+        match self {
+            Stale::Outside => panic!("SelfRefViews selector returned a view outside the owner"),
+            Stale::Offset(err) => err,
+        }
+    }
+}
+
+impl<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset + Nullable>
+    SelfRefViews<O, V, N, I>
+{
+    /// Creates a new container and records the views picked by `select`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a selected view does not lie inside the owner.
+    ///
+    /// # Parameters
+    /// * `owner` - Value to be owned by the container.
+    /// * `select` - Function returning the views into the owner, in index order.
+    ///
+    /// # Returns
+    /// * `Result<Self, I::Error>` - `Ok` with initialised views, or the offset error when `I`
+    ///   cannot encode the distance to one of them.
+    pub fn new(owner: O, select: for<'a> fn(&'a O) -> [&'a V; N]) -> Result<Self, I::Error> {
+        let mut this = Self {
+            owner,
+            views: [SelfRef::null(); N],
+            select,
+        };
+        this.refresh().map_err(Stale::into_offset)?;
+        Ok(this)
+    }
+
+    /// Re-runs the selector and re-records every view.
+    ///
+    /// The new views replace the old ones only once all of them are accepted; on failure
+    /// every view is left unset.
+    fn refresh(&mut self) -> Result<(), Stale<I::Error>> {
+        let start = &self.owner as *const O as usize;
+        let end = start + size_of::<O>();
+        let mut fresh = [SelfRef::null(); N];
+        let views = (self.select)(&self.owner);
+        let mut result = Ok(());
+        for (index, (slot, view)) in fresh.iter_mut().zip(views).enumerate() {
+            let addr = view as *const V as *const u8 as usize;
+            if addr < start || addr + size_of_val(view) > end {
+                result = Err(Stale::Outside);
+                break;
+            }
+            // Measured from where the view will be stored, not from `fresh`.
+            let at = &self.views[index] as *const SelfRef<V, I> as *mut u8;
+            match I::sub(addr as *mut u8, at) {
+                Ok(offset) => *slot = SelfRef::from_parts(offset, V::decompose(view)),
+                Err(err) => {
+                    result = Err(Stale::Offset(err));
+                    break;
+                }
+            }
+        }
+        match result {
+            Ok(()) => self.views = fresh,
+            Err(_) => self.unset(),
+        }
+        result
+    }
+
+    /// Leaves every view unset.
+    fn unset(&mut self) {
+        self.views = [SelfRef::null(); N];
+    }
+
+    /// Immutable access to the owner.
+    ///
+    /// # Returns
+    /// * `&O` - Shared reference to the owned value.
+    #[inline]
+    pub fn get(&self) -> &O {
+        &self.owner
+    }
+
+    /// Mutable access to the owner; the views are revalidated when the guard is finished
+    /// or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Dropping the guard panics if a view no longer lies inside the owner or its distance
+    /// no longer fits in `I`; use [`SelfRefViewsGuard::finish`] to handle the latter.
+    ///
+    /// # Returns
+    /// * `SelfRefViewsGuard<'_, O, V, N, I>` - Guard dereferencing to the owner.
+    #[inline]
+    pub fn get_mut(&mut self) -> SelfRefViewsGuard<'_, O, V, N, I> {
+        SelfRefViewsGuard { views: self }
+    }
+
+    /// Resolves the view at `index`, if it exists and is set.
+    ///
+    /// # Returns
+    /// * `Option<&V>` - Shared reference to the view, or `None` when `index >= N` or the
+    ///   views were left unset by a failed revalidation.
+    #[inline]
+    pub fn try_view(&self, index: usize) -> Option<&V> {
+        let view = self.views.get(index).filter(|view| view.is_ready())?;
+        let base = self as *const Self as *const u8;
+        Some(unsafe { view.get_ref_from_base_unchecked(base) })
+    }
+
+    /// Resolves the view at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= N` or the views were left unset by a failed revalidation.
+    ///
+    /// # Returns
+    /// * `&V` - Shared reference to the view.
+    #[inline]
+    pub fn view(&self, index: usize) -> &V {
+        self.try_view(index)
+            .expect("SelfRefViews index out of range or views unset")
+    }
+
+    /// Resolves the view at `index` mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= N` or the views were left unset by a failed revalidation.
+    ///
+    /// # Returns
+    /// * `&mut V` - Exclusive reference to the view.
+    #[inline]
+    pub fn view_mut(&mut self, index: usize) -> &mut V {
+        assert!(
+            self.views[index].is_ready(),
+            "SelfRefViews views unset by a failed revalidation"
+        );
+        let base = self as *mut Self as *mut u8;
+        unsafe { self.views[index].get_mut_from_base_unchecked(base) }
+    }
+
+    /// Resolves every view.
+    ///
+    /// # Returns
+    /// * `[&V; N]` - Shared references to the views, in index order.
+    pub fn views(&self) -> [&V; N] {
+        core::array::from_fn(|index| self.view(index))
+    }
+
+    /// Consumes the container and returns the owner.
+    ///
+    /// # Returns
+    /// * `O` - The owned value.
+    pub fn into_inner(self) -> O {
+        self.owner
+    }
+}
+
+impl<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset + Nullable>
+    SelfRefViewsGuard<'_, O, V, N, I>
+{
+    /// Releases the owner and revalidates the views.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a view no longer lies inside the owner, like [`SelfRefViews::new`]. The
+    /// views are unset before the panic.
+    ///
+    /// # Returns
+    /// * `Result<(), I::Error>` - `Ok` once every view is re-recorded, or the offset error
+    ///   for a view whose distance no longer fits in `I`, in which case every view is unset.
+    pub fn finish(self) -> Result<(), I::Error> {
+        let result = self.views.refresh();
+        core::mem::forget(self);
+        result.map_err(Stale::into_offset)
+    }
+}
+
+impl<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset + Nullable> Deref
+    for SelfRefViewsGuard<'_, O, V, N, I>
+{
+    type Target = O;
+
+    fn deref(&self) -> &O {
+        &self.views.owner
+    }
+}
+
+impl<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset + Nullable> DerefMut
+    for SelfRefViewsGuard<'_, O, V, N, I>
+{
+    fn deref_mut(&mut self) -> &mut O {
+        &mut self.views.owner
+    }
+}
+
+impl<O, V: ?Sized + PointerRecomposition, const N: usize, I: Offset + Nullable> Drop
+    for SelfRefViewsGuard<'_, O, V, N, I>
+{
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            self.views.unset();
+            return;
+        }
+        if self.views.refresh().map_err(Stale::into_offset).is_err() {
+            panic!("SelfRefViews view offset no longer fits in the offset type");
+        }
+    }
+}
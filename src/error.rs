@@ -10,13 +10,79 @@ pub struct IntegerOffsetError(pub(crate) IntegerOffsetErrorImpl);
 pub(crate) enum IntegerOffsetErrorImpl {
     /// Failed to convert isize to given integer type
     Conversion(isize),
+    /// A wide offset does not fit in isize
+    Range(i128),
     /// Failed to subtract the two usizes (overflowed isize)
     Sub(usize, usize),
+    /// The offset uses low bits reserved for a tag (offset, tag bits)
+    Tagged(isize, u32),
+}
+
+/// An error type for when a `SelfRef` cannot be reinterpreted as another target type
+#[derive(Debug)]
+pub struct CastError(pub(crate) CastErrorImpl);
+
+/// All types of cast errors, this is internal and so protected
+/// behind a wrapper struct
+#[derive(Debug)]
+pub(crate) enum CastErrorImpl {
+    /// Source and destination sizes differ (source bytes, destination bytes)
+    Size(usize, usize),
+    /// Target address is not aligned for the destination type (address, alignment)
+    Alignment(usize, usize),
+    /// The offset does not fit in isize, so it names no address
+    Range,
+    /// The offset to the target cannot be stored in the destination
+    Offset(IntegerOffsetError),
+    /// Target bytes are not valid UTF-8
+    Utf8(core::str::Utf8Error),
+    /// The pointer has not been initialised
+    Unset,
+}
+
+/// An error type for when a length does not fit in a compact metadata encoding
+#[derive(Debug)]
+pub struct LengthError(pub(crate) LengthErrorImpl);
+
+/// All types of length errors, this is internal and so protected
+/// behind a wrapper struct
+#[derive(Debug)]
+pub(crate) enum LengthErrorImpl {
+    /// The length exceeds the encoding's range (length, encoding bits)
+    Overflow(usize, u32),
+}
+
+/// An error type for fallible in-place construction
+#[derive(Debug)]
+pub enum InitError<E, O> {
+    /// The offset type cannot encode the distance to the value
+    Offset(O),
+    /// The initialiser reported an error
+    Init(E),
+}
+
+impl<O> InitError<core::convert::Infallible, O> {
+    /// Extracts the offset error from an infallible initialiser's result.
+    pub fn into_offset(self) -> O {
+        match self {
+            InitError::Offset(err) => err,
+            InitError::Init(never) => match never {},
+        }
+    }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for IntegerOffsetError {}
 
+#[cfg(feature = "std")]
+impl<E: std::error::Error, O: std::error::Error> std::error::Error for InitError<E, O> {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
 mod fmt {
     use super::*;
     use std::fmt;
@@ -29,9 +95,55 @@ mod fmt {
                     "Offset could not be stored (offset of {} is too large)",
                     del
                 ),
+                IntegerOffsetErrorImpl::Range(del) => {
+                    write!(f, "Offset of {} does not fit in `isize`", del)
+                }
                 IntegerOffsetErrorImpl::Sub(a, b) => {
                     write!(f, "Difference is beween {} and {} overflows `isize`", a, b)
                 }
+                IntegerOffsetErrorImpl::Tagged(del, bits) => {
+                    write!(f, "Offset of {} leaves no room for a {}-bit tag", del, bits)
+                }
+            }
+        }
+    }
+
+    impl<E: fmt::Display, O: fmt::Display> fmt::Display for InitError<E, O> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                InitError::Offset(err) => write!(f, "{}", err),
+                InitError::Init(err) => write!(f, "Initialiser failed ({})", err),
+            }
+        }
+    }
+
+    impl fmt::Display for CastError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match &self.0 {
+                CastErrorImpl::Size(from, to) => write!(
+                    f,
+                    "Cannot reinterpret {} bytes as a {} byte target",
+                    from, to
+                ),
+                CastErrorImpl::Alignment(addr, align) => write!(
+                    f,
+                    "Target address {:#x} is not aligned to {} bytes",
+                    addr, align
+                ),
+                CastErrorImpl::Range => write!(f, "Offset lies outside the address space"),
+                CastErrorImpl::Offset(err) => write!(f, "{}", err),
+                CastErrorImpl::Utf8(err) => write!(f, "Target bytes are not UTF-8 ({})", err),
+                CastErrorImpl::Unset => write!(f, "Cannot cast an unset relative pointer"),
+            }
+        }
+    }
+
+    impl fmt::Display for LengthError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.0 {
+                LengthErrorImpl::Overflow(len, bits) => {
+                    write!(f, "Length {} does not fit in {} bits", len, bits)
+                }
             }
         }
     }
@@ -61,7 +61,8 @@ use any type implementing `Delta`: `i8`, `i16`, `i32`, `i64`, `i128`, `isize`.
 The tradeoff: smaller offset types reduce addressable range.
 `isize` covers at least half of addressable memory. For self-referential
 structures, choose an offset type whose range exceeds your structure size:
-`std::mem::size_of::<YourStruct>() <= I::MAX`.
+`std::mem::size_of::<YourStruct>() <= I::MAX`. The `assert_offset_fits!` macro
+checks this at compile time.
 
 Note: Unsized types require additional considerations.
 
@@ -148,12 +149,35 @@ mod macros;
 mod metadata;
 mod offset;
 mod pointer;
+mod sync;
 mod combinators {
+    pub mod const_table;
+    pub mod rebuild_cell;
     pub mod self_ref_cell;
+    #[cfg(any(
+        all(test, loom),
+        feature = "portable-atomic",
+        target_has_atomic = "ptr"
+    ))]
+    pub mod self_ref_lock;
+    pub mod self_ref_ref_cell;
+    pub mod self_ref_views;
 }
 
-pub use self::combinators::self_ref_cell::SelfRefCell;
+pub use self::combinators::const_table::{ConstPool, ConstTable};
+pub use self::combinators::rebuild_cell::{BuildFn, DependentFamily, Rebuild, RebuildCell};
+pub use self::combinators::self_ref_cell::{CellSlot, FilledSlot, SelfRefCell};
+#[cfg(any(
+    all(test, loom),
+    feature = "portable-atomic",
+    target_has_atomic = "ptr"
+))]
+pub use self::combinators::self_ref_lock::{SelfRefLock, SelfRefReadGuard, SelfRefWriteGuard};
+pub use self::combinators::self_ref_ref_cell::{SelfRefBorrow, SelfRefBorrowMut, SelfRefRefCell};
+pub use self::combinators::self_ref_views::{SelfRefViews, SelfRefViewsGuard};
 pub use self::error::*;
+#[doc(hidden)]
+pub use self::macros::__array_elements;
 pub use self::metadata::*;
 pub use self::offset::*;
 pub use self::pointer::*;
@@ -1,4 +1,5 @@
 #[doc(hidden)]
+#[deprecated(note = "declare the struct with `self_referential!` instead")]
 #[macro_export]
 macro_rules! selfref_accessors {
     (impl $owner:ty { $get:ident, $get_mut:ident : $field:ident -> $t:ty }) => {
@@ -19,3 +20,719 @@ macro_rules! selfref_accessors {
         }
     };
 }
+
+/// Implements `PointerRecomposition` for trait-object types on stable Rust.
+///
+/// Each listed `dyn Trait` gets an implementation that stores the vtable half of the
+/// fat pointer as [`DynVTable`](crate::DynVTable), so `SelfRef<dyn Trait>` works without
+/// the `nightly` feature. The crate already covers `dyn Any`, `dyn Debug` and
+/// `dyn Display`, each with their `Send`/`Sync` variants; invoke the macro for your own
+/// traits.
+///
+/// ```rust
+/// use movable_ref::{dyn_recomposition, SelfRef};
+///
+/// trait Shape {
+///     fn area(&self) -> u32;
+/// }
+///
+/// struct Square(u32);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> u32 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// dyn_recomposition!(dyn Shape);
+///
+/// struct Holder {
+///     square: Square,
+///     shape: SelfRef<dyn Shape, i8>,
+/// }
+///
+/// let mut holder = Holder { square: Square(3), shape: SelfRef::null() };
+/// holder.shape.set(&mut holder.square).unwrap();
+///
+/// let holder = Box::new(holder);
+/// let base = &*holder as *const Holder as *const u8;
+/// assert_eq!(unsafe { holder.shape.get_ref_from_base_unchecked(base) }.area(), 9);
+/// ```
+///
+/// The implementation splits fat pointers assuming the data pointer comes first and the
+/// vtable second, which holds on every current compiler and is checked by the crate's
+/// tests.
+///
+/// Every entry must be spelled `dyn Trait`, optionally followed by `+` and further bounds,
+/// so only trait objects can be listed; slice- and `str`-tailed types belong in
+/// [`slice_tail_recomposition!`](crate::slice_tail_recomposition). Generic arguments are
+/// accepted as long as they contain no commas; name such a trait through an alias trait.
+///
+/// ```compile_fail
+/// use movable_ref::dyn_recomposition;
+///
+/// struct Packet<T: ?Sized> {
+///     kind: u8,
+///     payload: T,
+/// }
+///
+/// dyn_recomposition!(Packet<[u16]>);
+/// ```
+#[macro_export]
+macro_rules! dyn_recomposition {
+    () => {};
+    (dyn $($rest:tt)+) => {
+        $crate::dyn_recomposition!(@bounds [] $($rest)+);
+    };
+    // Collects one entry's bounds up to the next top-level comma.
+    (@bounds [$($bound:tt)+] , $($rest:tt)*) => {
+        $crate::dyn_recomposition!(@impl dyn $($bound)+);
+        $crate::dyn_recomposition!($($rest)*);
+    };
+    (@bounds [$($bound:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::dyn_recomposition!(@bounds [$($bound)* $next] $($rest)*);
+    };
+    (@bounds [$($bound:tt)+]) => {
+        $crate::dyn_recomposition!(@impl dyn $($bound)+);
+    };
+    (@impl $t:ty) => {
+        unsafe impl $crate::PointerRecomposition for $t {
+            type Components = $crate::DynVTable;
+
+            #[inline]
+            fn decompose(this: &Self) -> $crate::DynVTable {
+                $crate::__dyn_vtable(this as *const Self)
+            }
+
+            #[inline]
+            unsafe fn recompose(
+                ptr: $crate::Ptr<u8>,
+                vtable: $crate::DynVTable,
+            ) -> $crate::Ptr<Self> {
+                ::core::ptr::NonNull::new($crate::__dyn_from_parts::<Self>(ptr?.as_ptr(), vtable))
+            }
+        }
+    };
+}
+
+/// Implements `PointerRecomposition` for structs whose last field is a slice.
+///
+/// Custom dynamically sized types such as `struct Packet { len: u16, payload: [u8] }`
+/// carry the tail's element count as pointer metadata. The generated implementation
+/// stores that count and rebuilds the fat pointer from it, so padding and alignment are
+/// handled by the compiler. A generic struct is listed on its own with an `impl<..>` prefix,
+/// as in `slice_tail_recomposition!(impl<T> Packet<T, [u8]>)`.
+///
+/// ```rust
+/// use movable_ref::{slice_tail_recomposition, SelfRef};
+///
+/// struct Packet<T: ?Sized> {
+///     kind: u8,
+///     payload: T,
+/// }
+///
+/// slice_tail_recomposition!(Packet<[u16]>);
+///
+/// struct Frame {
+///     packet: Packet<[u16; 3]>,
+///     view: SelfRef<Packet<[u16]>, i8>,
+/// }
+///
+/// let mut frame = Frame { packet: Packet { kind: 1, payload: [7, 8, 9] }, view: SelfRef::null() };
+/// frame.view.set(&mut frame.packet).unwrap();
+///
+/// let frame = Box::new(frame);
+/// let base = &*frame as *const Frame as *const u8;
+/// let packet = unsafe { frame.view.get_ref_from_base_unchecked(base) };
+/// assert_eq!((packet.kind, &packet.payload[..]), (1, &[7, 8, 9][..]));
+/// ```
+///
+/// Listing a type whose tail is not a slice fails to compile.
+#[macro_export]
+macro_rules! slice_tail_recomposition {
+    (impl<$($g:ident),* $(,)?> $t:ty) => {
+        unsafe impl<$($g),*> $crate::PointerRecomposition for $t {
+            type Components = usize;
+
+            #[inline]
+            fn decompose(this: &Self) -> usize {
+                // A slice of `()` shares the metadata of the tail and is valid at any address.
+                unsafe { &*(this as *const Self as *const [()]) }.len()
+            }
+
+            #[inline]
+            unsafe fn recompose(ptr: $crate::Ptr<u8>, len: usize) -> $crate::Ptr<Self> {
+                let tail = ::core::ptr::slice_from_raw_parts_mut(ptr?.as_ptr() as *mut (), len);
+                ::core::ptr::NonNull::new(tail as *mut Self)
+            }
+        }
+    };
+    ($($t:ty),+ $(,)?) => {
+        $($crate::slice_tail_recomposition!(impl<> $t);)+
+    };
+}
+
+/// Declares a trait-object wrapper whose pointer metadata is a small type index.
+///
+/// A `SelfRef<dyn Trait>` stores a vtable pointer next to its offset, which is as wide as
+/// a pointer and only meaningful inside the running process. The wrapper declared here
+/// wraps `dyn Trait` for a fixed list of implementing types and stores the position of
+/// the target's type in that list instead, as a `u8` or `u16`. The vtable is rebuilt from
+/// the index on access, so a buffer holding such pointers stays valid after being written
+/// to disk or shared with another process running the same binary.
+///
+/// The trait must have `Any` as a supertrait so the concrete type behind a trait object
+/// can be identified. `from_ref` and `from_mut` return `None` for unregistered types.
+/// Indices follow the order of the list, so append new types to keep persisted data valid.
+///
+/// ```rust
+/// use core::any::Any;
+/// use movable_ref::{vtable_registry, SelfRef};
+///
+/// trait Shape: Any {
+///     fn area(&self) -> u32;
+/// }
+///
+/// struct Square(u32);
+/// struct Rect(u32, u32);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> u32 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// impl Shape for Rect {
+///     fn area(&self) -> u32 {
+///         self.0 * self.1
+///     }
+/// }
+///
+/// vtable_registry! {
+///     /// Shapes that can be stored behind a compact pointer.
+///     struct Shapes(dyn Shape): u8 { Square, Rect }
+/// }
+///
+/// struct Holder {
+///     rect: Rect,
+///     shape: SelfRef<Shapes, i8>,
+/// }
+///
+/// let mut holder = Holder { rect: Rect(2, 5), shape: SelfRef::null() };
+/// holder.shape.set(Shapes::from_mut(&mut holder.rect).unwrap()).unwrap();
+///
+/// let holder = Box::new(holder);
+/// let base = &*holder as *const Holder as *const u8;
+/// let shape = unsafe { holder.shape.get_ref_from_base_unchecked(base) };
+/// assert_eq!((shape.index(), shape.area()), (1, 10));
+/// ```
+///
+/// Listing more types than the index type can count fails to compile.
+#[macro_export]
+macro_rules! vtable_registry {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($dyn:ty): $index:ty { $($member:ty),+ $(,)? }
+    ) => {
+        $(#[$meta])*
+        #[repr(transparent)]
+        $vis struct $name($dyn);
+
+        const _: () = assert!(
+            [$(::core::stringify!($member)),+].len() - 1 <= <$index>::MAX as usize,
+            "too many types for the registry's index type"
+        );
+
+        impl $name {
+            /// Wraps a trait object whose concrete type is registered.
+            ///
+            /// # Parameters
+            /// * `value` - Trait object to wrap.
+            ///
+            /// # Returns
+            /// * `Option<&Self>` - The wrapper, or `None` if the type is not registered.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn from_ref(value: &$dyn) -> ::core::option::Option<&Self> {
+                Self::lookup(value)?;
+                Some(unsafe { &*(value as *const $dyn as *const Self) })
+            }
+
+            /// Wraps a mutable trait object whose concrete type is registered.
+            ///
+            /// # Parameters
+            /// * `value` - Trait object to wrap.
+            ///
+            /// # Returns
+            /// * `Option<&mut Self>` - The wrapper, or `None` if the type is not registered.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn from_mut(value: &mut $dyn) -> ::core::option::Option<&mut Self> {
+                Self::lookup(value)?;
+                Some(unsafe { &mut *(value as *mut $dyn as *mut Self) })
+            }
+
+            /// Position of the wrapped value's type in the registry.
+            ///
+            /// # Returns
+            /// * The index stored as pointer metadata.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn index(&self) -> $index {
+                match Self::lookup(&self.0) {
+                    Some(index) => index,
+                    None => unreachable!("wrapped type is registered"),
+                }
+            }
+
+            #[allow(unused_assignments)]
+            fn lookup(value: &$dyn) -> ::core::option::Option<$index> {
+                // Dispatches through the vtable to the concrete type's `Any` impl.
+                let id = ::core::any::Any::type_id(value);
+                // Counted in `usize` so stepping past the last member of a full registry
+                // cannot overflow; the assertion above keeps every position in range.
+                let mut index: usize = 0;
+                $(
+                    if id == ::core::any::TypeId::of::<$member>() {
+                        return Some(index as $index);
+                    }
+                    index += 1;
+                )+
+                None
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = $dyn;
+
+            #[inline]
+            fn deref(&self) -> &$dyn {
+                &self.0
+            }
+        }
+
+        impl ::core::ops::DerefMut for $name {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut $dyn {
+                &mut self.0
+            }
+        }
+
+        unsafe impl $crate::PointerRecomposition for $name {
+            type Components = $index;
+
+            #[inline]
+            fn decompose(this: &Self) -> $index {
+                this.index()
+            }
+
+            #[allow(unused_assignments)]
+            #[inline]
+            unsafe fn recompose(ptr: $crate::Ptr<u8>, index: $index) -> $crate::Ptr<Self> {
+                let ptr = ptr?.as_ptr();
+                let mut current: usize = 0;
+                $(
+                    if index as usize == current {
+                        // The unsizing cast materialises the member's vtable locally.
+                        let object = ptr as *mut $member as *mut $dyn;
+                        return ::core::ptr::NonNull::new(object as *mut Self);
+                    }
+                    current += 1;
+                )+
+                None
+            }
+        }
+    };
+}
+
+/// Creates a [`FieldRef`](crate::FieldRef) to a field of a sized struct.
+///
+/// `field_ref!(Owner, field)` computes the field's offset with `core::mem::offset_of!`
+/// and infers the field's type, producing a zero-sized value that can initialise a
+/// `const`. `Owner` must be a concrete type.
+///
+/// ```rust
+/// use movable_ref::field_ref;
+///
+/// struct Pair(u8, [u16; 2]);
+///
+/// let second = field_ref!(Pair, 1);
+/// let pair = Pair(1, [2, 3]);
+/// assert_eq!(second.get(&pair), &[2, 3]);
+/// ```
+///
+/// `offset_of!` is available from Rust 1.77; on older compilers write the offset out
+/// and use the unsafe [`FieldRef::new`](crate::FieldRef::new).
+#[macro_export]
+macro_rules! field_ref {
+    ($owner:ty, $field:tt) => {{
+        const OFFSET: usize = ::core::mem::offset_of!($owner, $field);
+        // SAFETY: the offset and the field type both come from the field itself.
+        unsafe {
+            $crate::FieldRef::<$owner, _, OFFSET>::__with_accessor(|owner: &$owner| &owner.$field)
+        }
+    }};
+}
+
+/// Fails to compile unless offsets of type `I` can span every byte of `Owner`.
+///
+/// A `SelfRef<T, I>` stored inside `Owner` and pointing elsewhere in it needs a distance
+/// of up to `size_of::<Owner>() - 1` bytes in either direction. When the offset type is
+/// too narrow, `set` only reports that at runtime; this assertion catches it when the
+/// struct grows instead. `Owner` must be a concrete type, and the offset one of the
+/// primitive integers implementing [`IntegerOffset`](crate::IntegerOffset).
+///
+/// ```rust
+/// use movable_ref::{assert_offset_fits, SelfRef};
+///
+/// struct Small {
+///     data: [u8; 64],
+///     ptr: SelfRef<u8, i8>,
+/// }
+///
+/// assert_offset_fits!(Small, i8);
+/// ```
+///
+/// ```compile_fail
+/// use movable_ref::{assert_offset_fits, SelfRef};
+///
+/// struct Large {
+///     data: [u8; 200],
+///     ptr: SelfRef<[u8], i8>,
+/// }
+///
+/// assert_offset_fits!(Large, i8);
+/// ```
+#[macro_export]
+macro_rules! assert_offset_fits {
+    ($owner:ty, $offset:ty $(,)?) => {
+        const _: () = assert!(
+            $crate::__offset_fits::<$offset>(::core::mem::size_of::<$owner>()),
+            // Passed as an argument so braces in the type names are not read as formatting.
+            "{}",
+            concat!(
+                "`",
+                stringify!($owner),
+                "` is too large for `",
+                stringify!($offset),
+                "` offsets"
+            )
+        );
+    };
+}
+
+/// Names the narrowest integer offset able to span a value of type `Owner`.
+///
+/// Expands to [`OffsetOfWidth`](crate::OffsetOfWidth) applied to
+/// [`smallest_offset_width`](crate::smallest_offset_width) of `size_of::<Owner>()`, so the
+/// width follows the type as it grows, with no runtime cost. `Owner` must be a concrete
+/// type.
+///
+/// Naming the owner from inside its own definition, as in `auto_offset!(Cursor)` within
+/// `Cursor`, is a cycle error, since the offset is part of the size it would be derived
+/// from. Declare the owner with [`self_referential!`](crate::self_referential) and
+/// `refs(auto)` to size its pointers from the owner itself, or name the part the pointers
+/// span, as below, and check the whole struct with
+/// [`assert_offset_fits!`](crate::assert_offset_fits).
+///
+/// ```rust
+/// use movable_ref::{assert_offset_fits, auto_offset, SelfRef};
+///
+/// struct Buffer {
+///     bytes: [u8; 300],
+///     len: u16,
+/// }
+///
+/// struct Cursor {
+///     buffer: Buffer,
+///     at: SelfRef<u8, auto_offset!(Buffer)>,
+/// }
+///
+/// assert_offset_fits!(Cursor, auto_offset!(Buffer));
+/// assert_eq!(core::mem::size_of::<auto_offset!(Buffer)>(), 2);
+/// ```
+///
+/// ```compile_fail
+/// use movable_ref::{auto_offset, SelfRef};
+///
+/// struct Cursor {
+///     bytes: [u8; 300],
+///     at: SelfRef<u8, auto_offset!(Cursor)>,
+/// }
+/// ```
+#[macro_export]
+macro_rules! auto_offset {
+    ($owner:ty) => {
+        $crate::OffsetOfWidth<{ $crate::smallest_offset_width(::core::mem::size_of::<$owner>()) }>
+    };
+}
+
+/// Declares a movable self-referential struct together with its constructor and accessors.
+///
+/// The first block lists the struct's data fields, the `refs` block its relative pointers.
+/// Each reference gives the `SelfRef` type, the accessors to generate and its target: a
+/// data field, written `= field`, or an element or sub-slice of an array field, written
+/// `= field[index]` with any index or range. The generated `new` takes the data fields in
+/// order, points every reference at its target and returns `Err(IntegerOffsetError)` when
+/// an offset type cannot encode the distance. It panics if an index is out of bounds.
+///
+/// Accessors are named explicitly, any subset in any order:
+///
+/// * `get: name` - `fn name(&self) -> &T`, panicking if the pointer is unset
+/// * `get_mut: name` - `fn name(&mut self) -> &mut T`, panicking if the pointer is unset
+/// * `try_get: name` - `fn name(&self) -> Option<&T>`
+/// * `try_get_mut: name` - `fn name(&mut self) -> Option<&mut T>`
+///
+/// They take the reference's visibility and doc comments; the `SelfRef` fields themselves
+/// stay private. `new` leaves every pointer set, but code in the defining module can still
+/// reach the fields and `reset` one, which the accessors report rather than trust. The struct may have type parameters bounded by traits joined with `+`,
+/// each named by a plain path such as `Copy` or `core::fmt::Debug`. Generic arguments and
+/// lifetimes in bounds are not accepted; declare a helper trait for those.
+///
+/// ```rust
+/// use movable_ref::{self_referential, PointerRecomposition};
+///
+/// self_referential! {
+///     /// A fixed-size record with views into its own buffer.
+///     pub struct Record<T: Copy + PointerRecomposition> {
+///         pub items: [T; 4],
+///         header: [u8; 8],
+///     }
+///     refs {
+///         /// The first item.
+///         pub first: SelfRef<T, i8> { get: first, get_mut: first_mut } = items[0],
+///         /// The header after its tag bytes.
+///         pub(crate) body: SelfRef<[u8], i16> { get: body, try_get: try_body } = header[2..],
+///     }
+/// }
+///
+/// let mut record = Record::new([1u16, 2, 3, 4], *b"ABcdefgh").unwrap();
+/// *record.first_mut() = 9;
+///
+/// let record = Box::new(record);
+/// assert_eq!((*record.first(), record.items[0]), (9, 9));
+/// assert_eq!(record.body(), b"cdefgh");
+/// assert_eq!(record.try_body(), Some(&b"cdefgh"[..]));
+/// ```
+///
+/// Writing `refs(auto)` instead of `refs` sizes every pointer from the struct itself: the
+/// offset type is the narrowest integer spanning the struct's fields laid out with
+/// pointer-sized offsets, which bounds the final size without depending on it, and a
+/// compile-time [`assert_offset_fits!`](crate::assert_offset_fits) confirms the choice.
+/// The pointers then name no offset type, and the struct cannot be generic.
+///
+/// ```rust
+/// use movable_ref::self_referential;
+///
+/// self_referential! {
+///     struct Cursor {
+///         bytes: [u8; 300],
+///     }
+///     refs(auto) {
+///         at: SelfRef<u8> { get: at } = bytes[299],
+///     }
+/// }
+///
+/// let cursor = Box::new(Cursor::new([7; 300]).unwrap());
+/// assert_eq!(*cursor.at(), 7);
+/// ```
+///
+/// The macro builds each target place itself, so every target lies inside the struct and
+/// keeps its address and type for as long as the struct lives. Indexing is limited to
+/// arrays; any other field type is rejected at compile time.
+///
+/// ```compile_fail
+/// use movable_ref::self_referential;
+///
+/// self_referential! {
+///     struct Outside {
+///         bytes: Vec<u8>,
+///     }
+///     refs {
+///         first: SelfRef<u8> { get: first } = bytes[0],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! self_referential {
+    (@accessors $r:ident, $t:ty, $docs:tt, $v:vis, { $($kind:ident : $acc:ident),* $(,)? }) => {
+        $($crate::self_referential!(@accessor $kind, $acc, $r, $t, $docs, $v);)*
+    };
+    (@accessor get, $acc:ident, $r:ident, $t:ty, [$(#[$m:meta])*], $v:vis) => {
+        $(#[$m])*
+        #[allow(dead_code)]
+        #[inline]
+        $v fn $acc(&self) -> &$t {
+            ::core::assert!(
+                self.$r.is_ready(),
+                ::core::concat!("`", ::core::stringify!($r), "` is unset")
+            );
+            let base = self as *const Self as *const u8;
+            // SAFETY: only `new` sets the private field, at a place inside `Self`.
+            unsafe { self.$r.get_ref_from_base_unchecked(base) }
+        }
+    };
+    (@accessor get_mut, $acc:ident, $r:ident, $t:ty, [$(#[$m:meta])*], $v:vis) => {
+        $(#[$m])*
+        #[allow(dead_code)]
+        #[inline]
+        $v fn $acc(&mut self) -> &mut $t {
+            ::core::assert!(
+                self.$r.is_ready(),
+                ::core::concat!("`", ::core::stringify!($r), "` is unset")
+            );
+            let base = self as *mut Self as *mut u8;
+            // SAFETY: only `new` sets the private field, at a place inside `Self`.
+            unsafe { self.$r.get_mut_from_base_unchecked(base) }
+        }
+    };
+    (@accessor try_get, $acc:ident, $r:ident, $t:ty, [$(#[$m:meta])*], $v:vis) => {
+        $(#[$m])*
+        #[allow(dead_code)]
+        #[inline]
+        $v fn $acc(&self) -> ::core::option::Option<&$t> {
+            let base = self as *const Self as *const u8;
+            // SAFETY: only `new` sets the private field, at a place inside `Self`.
+            self.$r
+                .is_ready()
+                .then(|| unsafe { self.$r.get_ref_from_base_unchecked(base) })
+        }
+    };
+    (@accessor try_get_mut, $acc:ident, $r:ident, $t:ty, [$(#[$m:meta])*], $v:vis) => {
+        $(#[$m])*
+        #[allow(dead_code)]
+        #[inline]
+        $v fn $acc(&mut self) -> ::core::option::Option<&mut $t> {
+            let base = self as *mut Self as *mut u8;
+            // SAFETY: only `new` sets the private field, at a place inside `Self`.
+            self.$r
+                .is_ready()
+                .then(|| unsafe { self.$r.get_mut_from_base_unchecked(base) })
+        }
+    };
+    (@target $this:ident, $field:ident) => {
+        &mut $this.$field
+    };
+    (@target $this:ident, $field:ident [$index:expr]) => {
+        &mut $crate::__array_elements(&mut $this.$field)[$index]
+    };
+    (
+        @auto($offset:ty)
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$($g:ident $(: $($b:ident)::+ $(+ $($bs:ident)::+)*)?),+ $(,)?>)? {
+            $($(#[$fmeta:meta])* $fvis:vis $field:ident : $fty:ty),* $(,)?
+        }
+        refs {
+            $(
+                $(#[$rmeta:meta])*
+                $rvis:vis $r:ident : SelfRef<$t:ty> { $($accessors:tt)* }
+                    = $target:ident $([$index:expr])?
+            ),+ $(,)?
+        }
+    ) => {
+        $crate::self_referential! {
+            $(#[$meta])*
+            $vis struct $name $(<$($g $(: $($b)::+ $(+ $($bs)::+)*)?),+>)? {
+                $($(#[$fmeta])* $fvis $field: $fty),*
+            }
+            refs {
+                $(
+                    $(#[$rmeta])*
+                    $rvis $r: SelfRef<$t, $offset> { $($accessors)* } = $target $([$index])?
+                ),+
+            }
+        }
+        $crate::assert_offset_fits!($name, $offset);
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$($g:ident $(: $($b:ident)::+ $(+ $($bs:ident)::+)*)?),+ $(,)?>)? {
+            $($(#[$fmeta:meta])* $fvis:vis $field:ident : $fty:ty),* $(,)?
+        }
+        refs {
+            $(
+                $(#[$rmeta:meta])*
+                $rvis:vis $r:ident : SelfRef<$t:ty $(, $off:ty)?> { $($accessors:tt)* }
+                    = $target:ident $([$index:expr])?
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name $(<$($g $(: $($b)::+ $(+ $($bs)::+)*)?),+>)? {
+            $($(#[$fmeta])* $fvis $field: $fty,)*
+            $($r: $crate::SelfRef<$t $(, $off)?>,)+
+        }
+
+        impl $(<$($g $(: $($b)::+ $(+ $($bs)::+)*)?),+>)? $name $(<$($g),+>)? {
+            #[doc = ::core::concat!(
+                "Creates a new `",
+                ::core::stringify!($name),
+                "` and points its references into it."
+            )]
+            #[allow(clippy::too_many_arguments)]
+            $vis fn new(
+                $($field: $fty),*
+            ) -> ::core::result::Result<Self, $crate::IntegerOffsetError> {
+                let mut this = Self {
+                    $($field,)*
+                    $($r: $crate::SelfRef::null(),)+
+                };
+                $(
+                    this.$r.set(
+                        $crate::self_referential!(@target this, $target $([$index])?)
+                    )?;
+                )+
+                Ok(this)
+            }
+
+            $(
+                $crate::self_referential!(
+                    @accessors $r, $t, [$(#[$rmeta])*], $rvis, { $($accessors)* }
+                );
+            )+
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident $(<$($g:ident $(: $($b:ident)::+ $(+ $($bs:ident)::+)*)?),+ $(,)?>)? {
+            $($(#[$fmeta:meta])* $fvis:vis $field:ident : $fty:ty),* $(,)?
+        }
+        refs(auto) {
+            $(
+                $(#[$rmeta:meta])*
+                $rvis:vis $r:ident : SelfRef<$t:ty> { $($accessors:tt)* }
+                    = $target:ident $([$index:expr])?
+            ),+ $(,)?
+        }
+    ) => {
+        // The struct's size depends on the width being chosen, so it is bounded by laying
+        // out the same fields with pointer-sized offsets; `@auto` checks the result.
+        $crate::self_referential! {
+            @auto($crate::OffsetOfWidth<{
+                $crate::smallest_offset_width(::core::mem::size_of::<(
+                    $($fty,)* $($crate::SelfRef<$t, isize>,)+
+                )>())
+            }>)
+            $(#[$meta])*
+            $vis struct $name $(<$($g $(: $($b)::+ $(+ $($bs)::+)*)?),+>)? {
+                $($(#[$fmeta])* $fvis $field: $fty),*
+            }
+            refs {
+                $(
+                    $(#[$rmeta])*
+                    $rvis $r: SelfRef<$t> { $($accessors)* } = $target $([$index])?
+                ),+
+            }
+        }
+    };
+}
+
+/// Views an array field of a `self_referential!` struct as a slice.
+///
+/// Indexed targets go through this function so that only arrays, whose elements live
+/// inside the struct, can be indexed.
+#[doc(hidden)]
+#[inline]
+pub fn __array_elements<T, const N: usize>(array: &mut [T; N]) -> &mut [T] {
+    array
+}
@@ -0,0 +1,145 @@
+use super::traits::PointerRecomposition;
+use crate::error::{LengthError, LengthErrorImpl};
+use crate::offset::Ptr;
+use core::ops::{Deref, DerefMut};
+use std::ptr::{slice_from_raw_parts_mut, NonNull};
+
+mod sealed {
+    /// Unsized types whose metadata is a length.
+    pub trait Sequence {
+        fn length(&self) -> usize;
+    }
+
+    impl<T> Sequence for [T] {
+        fn length(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl Sequence for str {
+        fn length(&self) -> usize {
+            self.len()
+        }
+    }
+}
+
+macro_rules! compact_len {
+    ($($(#[$meta:meta])* $name:ident($len:ty)),* $(,)?) => {$(
+        $(#[$meta])*
+        #[repr(transparent)]
+        pub struct $name<T: ?Sized>(T);
+
+        impl<T: ?Sized + sealed::Sequence> $name<T> {
+            #[doc = concat!("Wraps a slice or `str` whose length fits in `", stringify!($len), "`.")]
+            ///
+            /// # Returns
+            /// * `Result<&Self, LengthError>` - The wrapped value, or the error when it is too long.
+            pub fn from_ref(value: &T) -> Result<&Self, LengthError> {
+                check_len::<$len>(value.length())?;
+                Ok(unsafe { &*(value as *const T as *const Self) })
+            }
+
+            #[doc = concat!("Wraps a mutable slice or `str` whose length fits in `", stringify!($len), "`.")]
+            ///
+            /// # Returns
+            /// * `Result<&mut Self, LengthError>` - The wrapped value, or the error when it is too
+            ///   long.
+            pub fn from_mut(value: &mut T) -> Result<&mut Self, LengthError> {
+                check_len::<$len>(value.length())?;
+                Ok(unsafe { &mut *(value as *mut T as *mut Self) })
+            }
+        }
+
+        impl<T: ?Sized> Deref for $name<T> {
+            type Target = T;
+
+            #[inline]
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T: ?Sized> DerefMut for $name<T> {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut T {
+                &mut self.0
+            }
+        }
+
+        unsafe impl<T> PointerRecomposition for $name<[T]> {
+            type Components = $len;
+
+            #[inline]
+            fn decompose(this: &Self) -> Self::Components {
+                // Checked when the wrapper was created.
+                this.0.len() as $len
+            }
+
+            #[inline]
+            unsafe fn recompose(ptr: Ptr<u8>, data: Self::Components) -> Ptr<Self> {
+                let slice = slice_from_raw_parts_mut(ptr?.as_ptr().cast::<T>(), data as usize);
+                NonNull::new(slice as *mut Self)
+            }
+        }
+
+        unsafe impl PointerRecomposition for $name<str> {
+            type Components = $len;
+
+            #[inline]
+            fn decompose(this: &Self) -> Self::Components {
+                // Checked when the wrapper was created.
+                this.0.len() as $len
+            }
+
+            #[inline]
+            unsafe fn recompose(ptr: Ptr<u8>, data: Self::Components) -> Ptr<Self> {
+                let slice = slice_from_raw_parts_mut(ptr?.as_ptr(), data as usize);
+                NonNull::new(slice as *mut Self)
+            }
+        }
+    )*};
+}
+
+#[inline]
+fn check_len<L: TryFrom<usize>>(len: usize) -> Result<(), LengthError> {
+    match L::try_from(len) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(LengthError(LengthErrorImpl::Overflow(
+            len,
+            8 * core::mem::size_of::<L>() as u32,
+        ))),
+    }
+}
+
+compact_len! {
+    /// A slice or `str` whose length is stored in a `u8` when referenced by a `SelfRef`.
+    ///
+    /// Plain `[T]` and `str` pointees store their length as a `usize`, which dominates the
+    /// size of small pointers and differs between 32- and 64-bit hosts. Wrapping the target
+    /// stores a fixed-width length instead, checked when the wrapper is created:
+    ///
+    /// ```rust
+    /// use movable_ref::{Len8, SelfRef};
+    ///
+    /// struct Frame {
+    ///     bytes: [u8; 16],
+    ///     body: SelfRef<Len8<[u8]>, i8>,
+    /// }
+    ///
+    /// let mut frame = Frame { bytes: [7; 16], body: SelfRef::null() };
+    /// frame.body.set(Len8::from_mut(&mut frame.bytes[4..]).unwrap()).unwrap();
+    ///
+    /// let frame = Box::new(frame);
+    /// let base = &*frame as *const Frame as *const u8;
+    /// assert_eq!(unsafe { frame.body.get_ref_from_base_unchecked(base) }.len(), 12);
+    /// ```
+    Len8(u8),
+    /// A slice or `str` whose length is stored in a `u16` when referenced by a `SelfRef`.
+    ///
+    /// See [`Len8`] for details.
+    Len16(u16),
+    /// A slice or `str` whose length is stored in a `u32` when referenced by a `SelfRef`.
+    ///
+    /// See [`Len8`] for details.
+    Len32(u32),
+}
@@ -0,0 +1,94 @@
+use core::any::Any;
+use core::fmt::{Debug, Display};
+use core::marker::PhantomData;
+use core::mem::{size_of, transmute_copy};
+#[cfg(feature = "nightly")]
+use core::ptr::{from_raw_parts_mut, metadata, DynMetadata, Pointee};
+
+/// Vtable half of a trait-object pointer.
+///
+/// Used as the `Components` of `dyn Trait` pointees registered with
+/// [`dyn_recomposition!`](crate::dyn_recomposition), so `SelfRef<dyn Trait>` works on
+/// stable Rust.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DynVTable(pub(crate) *const ());
+
+// SAFETY: A vtable is immutable data with static lifetime.
+unsafe impl Send for DynVTable {}
+unsafe impl Sync for DynVTable {}
+
+/// Layout of a trait-object pointer on every supported compiler: data first, then vtable.
+///
+/// The language does not guarantee this layout, so it is checked by the tests and by a
+/// debug assertion whenever a pointer is split. The `nightly` feature avoids relying on it.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct FatPtr {
+    pub(crate) data: *const (),
+    pub(crate) vtable: *const (),
+}
+
+struct IsFat<T: ?Sized>(PhantomData<*const T>);
+
+impl<T: ?Sized> IsFat<T> {
+    const OK: () = assert!(
+        size_of::<*const T>() == size_of::<FatPtr>(),
+        "dyn_recomposition! requires a trait object type"
+    );
+}
+
+#[cfg(not(feature = "nightly"))]
+#[doc(hidden)]
+#[inline]
+pub fn __dyn_vtable<T: ?Sized>(ptr: *const T) -> DynVTable {
+    #[allow(clippy::let_unit_value)]
+    let () = IsFat::<T>::OK;
+    let parts: FatPtr = unsafe { transmute_copy(&ptr) };
+    debug_assert_eq!(parts.data, ptr as *const ());
+    DynVTable(parts.vtable)
+}
+
+#[cfg(not(feature = "nightly"))]
+#[doc(hidden)]
+#[inline]
+pub unsafe fn __dyn_from_parts<T: ?Sized>(data: *mut u8, vtable: DynVTable) -> *mut T {
+    #[allow(clippy::let_unit_value)]
+    let () = IsFat::<T>::OK;
+    let parts = FatPtr {
+        data: data as *const (),
+        vtable: vtable.0,
+    };
+    transmute_copy(&parts)
+}
+
+// With `ptr_metadata` available the pointer is split by the compiler instead, so only the
+// representation of `DynMetadata` itself (a single vtable pointer) is relied upon.
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+#[inline]
+pub fn __dyn_vtable<T>(ptr: *const T) -> DynVTable
+where
+    T: ?Sized + Pointee<Metadata = DynMetadata<T>>,
+{
+    #[allow(clippy::let_unit_value)]
+    let () = IsFat::<T>::OK;
+    DynVTable(unsafe { transmute_copy(&metadata(ptr)) })
+}
+
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+#[inline]
+pub unsafe fn __dyn_from_parts<T>(data: *mut u8, vtable: DynVTable) -> *mut T
+where
+    T: ?Sized + Pointee<Metadata = DynMetadata<T>>,
+{
+    #[allow(clippy::let_unit_value)]
+    let () = IsFat::<T>::OK;
+    from_raw_parts_mut(data as *mut (), transmute_copy(&vtable.0))
+}
+
+crate::dyn_recomposition! {
+    dyn Any, dyn Any + Send, dyn Any + Sync, dyn Any + Send + Sync,
+    dyn Debug, dyn Debug + Send, dyn Debug + Sync, dyn Debug + Send + Sync,
+    dyn Display, dyn Display + Send, dyn Display + Sync, dyn Display + Send + Sync,
+}
@@ -290,3 +290,55 @@ unsafe impl PointerRecomposition for str {
         NonNull::new(slice as *mut str)
     }
 }
+
+// `CStr`, `OsStr` and `Path` are byte-sequence DSTs: their pointer metadata is the length
+// of the encoded bytes, which `size_of_val` reports since the element size is one.
+
+unsafe impl PointerRecomposition for core::ffi::CStr {
+    type Components = usize;
+
+    #[inline]
+    fn decompose(this: &Self) -> Self::Components {
+        core::mem::size_of_val(this)
+    }
+
+    #[inline]
+    unsafe fn recompose(ptr: Ptr<u8>, data: Self::Components) -> Ptr<Self> {
+        let ptr = ptr?.as_ptr();
+        debug_assert!(data > 0 && *ptr.add(data - 1) == 0);
+        let slice = std::ptr::slice_from_raw_parts_mut(ptr, data);
+        NonNull::new(slice as *mut Self)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl PointerRecomposition for std::ffi::OsStr {
+    type Components = usize;
+
+    #[inline]
+    fn decompose(this: &Self) -> Self::Components {
+        core::mem::size_of_val(this)
+    }
+
+    #[inline]
+    unsafe fn recompose(ptr: Ptr<u8>, data: Self::Components) -> Ptr<Self> {
+        let slice = std::ptr::slice_from_raw_parts_mut(ptr?.as_ptr(), data);
+        NonNull::new(slice as *mut Self)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl PointerRecomposition for std::path::Path {
+    type Components = usize;
+
+    #[inline]
+    fn decompose(this: &Self) -> Self::Components {
+        core::mem::size_of_val(this)
+    }
+
+    #[inline]
+    unsafe fn recompose(ptr: Ptr<u8>, data: Self::Components) -> Ptr<Self> {
+        let slice = std::ptr::slice_from_raw_parts_mut(ptr?.as_ptr(), data);
+        NonNull::new(slice as *mut Self)
+    }
+}
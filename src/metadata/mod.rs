@@ -9,10 +9,18 @@ pub mod traits;
 /// Implementations of MetaData trait for various types
 pub mod impls;
 
+/// Slice and string wrappers with fixed-width length metadata
+pub mod compact;
+
+/// Trait object support on stable Rust through `dyn_recomposition!`
+pub mod dyn_vtable;
+
 /// Trait object support for nightly Rust (requires ptr_metadata feature)
 #[cfg(feature = "nightly")]
 pub mod trait_object;
 
+pub use compact::*;
+pub use dyn_vtable::*;
 pub use traits::*;
 
 #[cfg(feature = "nightly")]
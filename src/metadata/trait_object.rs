@@ -1,6 +1,7 @@
 use super::traits::PointerRecomposition;
 use crate::offset::Ptr;
 use std::mem;
+use std::ops::{Deref, DerefMut};
 use std::ptr::{self, NonNull, Pointee};
 
 /// A wrapper that enables trait objects to work seamlessly with `SelfRef`.
@@ -34,9 +35,7 @@ use std::ptr::{self, NonNull, Pointee};
 ///         };
 ///         
 ///         // Convert our data to a trait object and store it
-///         let trait_obj = unsafe {
-///             TraitObject::from_mut(&mut container.data as &mut dyn Any)
-///         };
+///         let trait_obj = TraitObject::from_mut(&mut container.data as &mut dyn Any);
 ///         container.any_ref.set(trait_obj).unwrap();
 ///         
 ///         container
@@ -55,10 +54,10 @@ use std::ptr::{self, NonNull, Pointee};
 /// # }
 /// ```
 ///
-/// # Safety
-///
-/// This type is `#[repr(transparent)]` and should only be used with actual trait objects.
-/// Using it with concrete types will lead to undefined behavior.
+/// The `Pointee<Metadata = DynMetadata<T>>` bound restricts `T` to trait objects, so
+/// wrapping is safe. The wrapper dereferences to `T`, and types registered with
+/// [`dyn_recomposition!`](crate::dyn_recomposition) can skip it entirely: under the
+/// `nightly` feature those implementations are built on `DynMetadata` as well.
 #[repr(transparent)]
 pub struct TraitObject<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>>(T);
 
@@ -68,11 +67,6 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     /// This creates a `TraitObject` wrapper around your trait object, enabling
     /// it to be stored in a `SelfRef`. The wrapper is zero-cost and transparent.
     ///
-    /// # Safety
-    ///
-    /// `T` must be an actual trait object (like `dyn Debug`, `dyn Any`, etc.).
-    /// Using this with concrete types will cause undefined behavior.
-    ///
     /// # Example
     ///
     /// ```rust
@@ -82,10 +76,11 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     ///
     /// let value = 42i32;
     /// let debug_obj: &dyn Debug = &value;
-    /// let wrapped = unsafe { TraitObject::from_ref(debug_obj) };
+    /// let wrapped = TraitObject::from_ref(debug_obj);
     /// ```
-    pub unsafe fn from_ref(t: &T) -> &Self {
-        mem::transmute(t)
+    pub fn from_ref(t: &T) -> &Self {
+        // SAFETY: `TraitObject<T>` is a transparent wrapper around `T`.
+        unsafe { mem::transmute(t) }
     }
 
     /// Wraps a mutable trait object reference for use with `SelfRef`.
@@ -93,11 +88,6 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     /// Like `from_ref`, but for mutable references. This is what you'll typically
     /// use when setting up self-referential structures.
     ///
-    /// # Safety
-    ///
-    /// `T` must be an actual trait object (like `dyn Debug`, `dyn Any`, etc.).
-    /// Using this with concrete types will cause undefined behavior.
-    ///
     /// # Example
     ///
     /// ```rust
@@ -107,10 +97,11 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     ///
     /// let mut value = vec![1, 2, 3];
     /// let debug_obj: &mut dyn Debug = &mut value;
-    /// let wrapped = unsafe { TraitObject::from_mut(debug_obj) };
+    /// let wrapped = TraitObject::from_mut(debug_obj);
     /// ```
-    pub unsafe fn from_mut(t: &mut T) -> &mut Self {
-        mem::transmute(t)
+    pub fn from_mut(t: &mut T) -> &mut Self {
+        // SAFETY: `TraitObject<T>` is a transparent wrapper around `T`.
+        unsafe { mem::transmute(t) }
     }
 
     /// Unwraps back to the original trait object reference.
@@ -126,7 +117,7 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     /// # use std::any::Any;
     /// # struct C { data: Vec<u8>, r: SelfRef<TraitObject<dyn Any>, i16> }
     /// # let mut c = C { data: vec![1u8, 2, 3], r: SelfRef::null() };
-    /// # let trait_obj = unsafe { TraitObject::from_mut(&mut c.data as &mut dyn Any) };
+    /// # let trait_obj = TraitObject::from_mut(&mut c.data as &mut dyn Any);
     /// # c.r.set(trait_obj).unwrap();
     /// let retrieved = unsafe { c.r.get_ref_from_base_unchecked(&c as *const _ as *const u8) };
     /// let original: &dyn Any = retrieved.as_ref();
@@ -143,6 +134,22 @@ impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> TraitObject<T> {
     }
 }
 
+impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> Deref for TraitObject<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> DerefMut for TraitObject<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
 unsafe impl<T: ?Sized + Pointee<Metadata = ptr::DynMetadata<T>>> PointerRecomposition
     for TraitObject<T>
 {
@@ -0,0 +1,121 @@
+use super::delta::Nullable;
+use crate::sync::Ordering;
+
+/// Trait for atomic integers that can store an [`Offset`](super::Offset).
+///
+/// Used by `AtomicSelfRef` to update a relative pointer from several threads.
+/// Implemented for the `core::sync::atomic` counterparts of the built-in offset integers
+/// and, with the `portable-atomic` feature, for their `portable_atomic` counterparts too.
+///
+/// # Safety
+///
+/// Implementations must behave like the matching `core::sync::atomic` type:
+/// every operation is atomic and respects the given memory orderings.
+pub unsafe trait AtomicOffset: Sized {
+    /// Plain offset type stored in the atomic.
+    type Offset: Nullable;
+
+    /// Creates a new atomic holding `offset`.
+    fn new(offset: Self::Offset) -> Self;
+
+    /// Loads the stored offset.
+    fn load(&self, order: Ordering) -> Self::Offset;
+
+    /// Stores a new offset.
+    fn store(&self, offset: Self::Offset, order: Ordering);
+
+    /// Stores a new offset, returning the previous one.
+    fn swap(&self, offset: Self::Offset, order: Ordering) -> Self::Offset;
+
+    /// Stores `new` if the current offset equals `current`.
+    ///
+    /// Returns the previous offset, as `Ok` when the exchange happened and `Err` otherwise.
+    fn compare_exchange(
+        &self,
+        current: Self::Offset,
+        new: Self::Offset,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Offset, Self::Offset>;
+
+    /// Like [`AtomicOffset::compare_exchange`], but allowed to fail spuriously.
+    fn compare_exchange_weak(
+        &self,
+        current: Self::Offset,
+        new: Self::Offset,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self::Offset, Self::Offset>;
+}
+
+macro_rules! impl_atomic_offset {
+    ($($atomic:ty => $type:ty),* $(,)?) => {$(
+        unsafe impl AtomicOffset for $atomic {
+            type Offset = $type;
+
+            #[inline]
+            fn new(offset: $type) -> Self {
+                Self::new(offset)
+            }
+
+            #[inline]
+            fn load(&self, order: Ordering) -> $type {
+                self.load(order)
+            }
+
+            #[inline]
+            fn store(&self, offset: $type, order: Ordering) {
+                self.store(offset, order)
+            }
+
+            #[inline]
+            fn swap(&self, offset: $type, order: Ordering) -> $type {
+                self.swap(offset, order)
+            }
+
+            #[inline]
+            fn compare_exchange(
+                &self,
+                current: $type,
+                new: $type,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$type, $type> {
+                self.compare_exchange(current, new, success, failure)
+            }
+
+            #[inline]
+            fn compare_exchange_weak(
+                &self,
+                current: $type,
+                new: $type,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$type, $type> {
+                self.compare_exchange_weak(current, new, success, failure)
+            }
+        }
+    )*};
+}
+
+// The `core` impls are replaced by `loom`'s while running loom models, and the
+// `portable-atomic` impls are added alongside them, so enabling the feature never
+// removes an implementation.
+macro_rules! impl_atomic_offsets {
+    ($($width:literal => $atomic:ident($type:ty)),* $(,)?) => {$(
+        #[cfg(all(not(all(test, loom)), target_has_atomic = $width))]
+        impl_atomic_offset!(core::sync::atomic::$atomic => $type);
+        #[cfg(all(test, loom))]
+        impl_atomic_offset!(loom::sync::atomic::$atomic => $type);
+        #[cfg(feature = "portable-atomic")]
+        impl_atomic_offset!(portable_atomic::$atomic => $type);
+    )*};
+}
+
+impl_atomic_offsets! {
+    "8" => AtomicI8(i8),
+    "16" => AtomicI16(i16),
+    "32" => AtomicI32(i32),
+    "64" => AtomicI64(i64),
+    "ptr" => AtomicIsize(isize),
+}
@@ -0,0 +1,51 @@
+use super::delta::{__offset_fits, Nullable};
+
+/// Byte width of the narrowest integer offset that can span a `size`-byte value.
+///
+/// # Parameters
+/// * `size` - Size of the value the offsets must cover, usually `size_of::<Owner>()`.
+///
+/// # Returns
+/// * `usize` - `1`, `2`, `4` or `8`, the size of the chosen `i8`, `i16`, `i32` or `i64`.
+pub const fn smallest_offset_width(size: usize) -> usize {
+    if __offset_fits::<i8>(size) {
+        1
+    } else if __offset_fits::<i16>(size) {
+        2
+    } else if __offset_fits::<i32>(size) {
+        4
+    } else {
+        8
+    }
+}
+
+/// Type-level byte width, mapped to an offset integer through [`SelectOffset`].
+pub struct OffsetWidth<const BYTES: usize>;
+
+/// Maps an [`OffsetWidth`] to the integer offset of that width.
+pub trait SelectOffset {
+    /// The offset integer.
+    type Offset: Nullable;
+}
+
+impl SelectOffset for OffsetWidth<1> {
+    type Offset = i8;
+}
+
+impl SelectOffset for OffsetWidth<2> {
+    type Offset = i16;
+}
+
+impl SelectOffset for OffsetWidth<4> {
+    type Offset = i32;
+}
+
+impl SelectOffset for OffsetWidth<8> {
+    type Offset = i64;
+}
+
+/// The integer offset that is `BYTES` bytes wide.
+///
+/// Combined with [`smallest_offset_width`] this picks an offset type from a size known at
+/// compile time; [`auto_offset!`](crate::auto_offset) does both steps for a type.
+pub type OffsetOfWidth<const BYTES: usize> = <OffsetWidth<BYTES> as SelectOffset>::Offset;
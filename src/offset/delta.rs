@@ -1,3 +1,4 @@
+use crate::error::IntegerOffsetError;
 use std::ptr::NonNull;
 
 /// A nullable pointer, using `NonNull<T>`
@@ -38,6 +39,52 @@ pub unsafe trait Offset: Copy + Eq {
     unsafe fn add(self, a: *const u8) -> *mut u8;
 }
 
+pub(super) mod sealed {
+    pub trait Sealed {}
+}
+
+/// An [`Offset`] backed by one of the primitive signed integers.
+///
+/// Adds the conversions used to re-encode, tag and inspect offsets. The trait is sealed
+/// and implemented for `i8`, `i16`, `i32`, `i64`, `i128` and `isize`, so it can grow
+/// without breaking downstream `Offset` implementations.
+pub trait IntegerOffset: Nullable<Error = IntegerOffsetError> + sealed::Sealed {
+    /// Most negative distance, in bytes, that the offset can encode.
+    const MIN_DISTANCE: isize;
+
+    /// Largest distance, in bytes, that the offset can encode.
+    const MAX_DISTANCE: isize;
+
+    /// Converts the offset to a pointer-sized signed distance.
+    ///
+    /// # Returns
+    /// * `Result<isize, IntegerOffsetError>` - The distance, or an error when an `i128`
+    ///   offset lies outside the range of `isize`.
+    fn try_into_isize(self) -> Result<isize, IntegerOffsetError>;
+
+    /// Narrows a pointer-sized signed distance into `Self`.
+    ///
+    /// # Parameters
+    /// * `del` - Distance in bytes.
+    ///
+    /// # Returns
+    /// * `Result<Self, IntegerOffsetError>` - The offset, or an error when `del` cannot be
+    ///   represented in `Self`.
+    fn try_from_isize(del: isize) -> Result<Self, IntegerOffsetError>;
+}
+
+/// Whether offsets of type `I` can link any two bytes of a `size`-byte value.
+///
+/// Used by [`assert_offset_fits!`](crate::assert_offset_fits).
+#[doc(hidden)]
+pub const fn __offset_fits<I: IntegerOffset>(size: usize) -> bool {
+    if size == 0 {
+        return true;
+    }
+    let span = (size - 1) as isize;
+    span <= I::MAX_DISTANCE && -span >= I::MIN_DISTANCE
+}
+
 /// A `Delta` type that has a null/zero value.
 ///
 /// # Safety
@@ -1,4 +1,5 @@
-use super::delta::{Nullable, Offset};
+use super::delta::sealed::Sealed;
+use super::delta::{IntegerOffset, Nullable, Offset};
 use crate::error::{IntegerOffsetError, IntegerOffsetErrorImpl};
 use crate::pointer::unreachable::{UncheckedOptionExt, OVERFLOW_SUB};
 
@@ -13,6 +14,40 @@ macro_rules! impl_delta_zeroable {
                     None => return Err(IntegerOffsetError(IntegerOffsetErrorImpl::Sub(a as usize, b as usize)))
                 };
 
+                Self::try_from_isize(del)
+            }
+
+            unsafe fn sub_unchecked(a: *mut u8, b: *mut u8) -> Self {
+                isize::checked_sub(a as usize as _, b as usize as _).unchecked_unwrap(OVERFLOW_SUB) as _
+            }
+
+            unsafe fn add(self, a: *const u8) -> *mut u8 {
+                <*const u8>::offset(a, self as isize) as *mut u8
+            }
+        }
+
+        impl Sealed for $type {}
+
+        impl IntegerOffset for $type {
+            const MIN_DISTANCE: isize = if std::mem::size_of::<Self>() < std::mem::size_of::<isize>() {
+                Self::MIN as isize
+            } else {
+                isize::MIN
+            };
+
+            const MAX_DISTANCE: isize = if std::mem::size_of::<Self>() < std::mem::size_of::<isize>() {
+                Self::MAX as isize
+            } else {
+                isize::MAX
+            };
+
+            #[inline]
+            fn try_into_isize(self) -> Result<isize, IntegerOffsetError> {
+                isize::try_from(self)
+                    .map_err(|_| IntegerOffsetError(IntegerOffsetErrorImpl::Range(self as i128)))
+            }
+
+            fn try_from_isize(del: isize) -> Result<Self, IntegerOffsetError> {
                 if std::mem::size_of::<Self>() < std::mem::size_of::<isize>() && (
                     (Self::MIN as isize) > del ||
                     (Self::MAX as isize) < del
@@ -23,14 +58,6 @@ macro_rules! impl_delta_zeroable {
                     Ok(del as _)
                 }
             }
-
-            unsafe fn sub_unchecked(a: *mut u8, b: *mut u8) -> Self {
-                isize::checked_sub(a as usize as _, b as usize as _).unchecked_unwrap(OVERFLOW_SUB) as _
-            }
-
-            unsafe fn add(self, a: *const u8) -> *mut u8 {
-                <*const u8>::offset(a, self as isize) as *mut u8
-            }
         }
 
         impl Nullable for $type {
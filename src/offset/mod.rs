@@ -3,7 +3,11 @@
 //! This module contains the Offset trait and implementations for different
 //! integer types used to calculate offsets between memory locations.
 
+mod atomic;
+mod auto;
 mod delta;
 mod integers;
 
+pub use atomic::*;
+pub use auto::*;
 pub use delta::*;
@@ -0,0 +1,332 @@
+//! Atomic relative pointers
+//!
+//! This module contains the concurrent counterparts of `SelfRef`, which store
+//! their offset in an atomic integer.
+
+use crate::offset::{AtomicOffset, Nullable, Offset};
+use crate::sync::{AtomicIsize, Ordering};
+use core::fmt;
+use core::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// Resolves `offset` relative to `this`, deriving the result from `base`.
+#[inline]
+unsafe fn resolve_from_base<S, I: Nullable, T>(
+    this: &S,
+    offset: I,
+    base: *const u8,
+) -> Option<NonNull<T>> {
+    if offset == I::NULL {
+        return None;
+    }
+    let self_ptr = this as *const S as *const u8;
+    let d_self = self_ptr.offset_from(base);
+    let at_self = base.wrapping_offset(d_self);
+    NonNull::new(offset.add(at_self).cast::<T>())
+}
+
+/// A relative pointer whose offset can be updated atomically from several threads.
+///
+/// `AtomicSelfRef` is to [`SelfRef`](crate::SelfRef) what `AtomicPtr` is to a raw pointer.
+/// It stores the distance to a sized target in an atomic integer `A`, so a movable
+/// structure can hold lock-free links between its own fields. All operations work in
+/// offset space; [`AtomicSelfRef::offset_to`] and [`AtomicSelfRef::resolve`] convert
+/// between offsets and pointers measured from the atomic's current location.
+///
+/// The null offset marks an unset pointer, so a target can never be the atomic itself.
+///
+/// ```rust
+/// use movable_ref::AtomicSelfRef;
+/// use std::sync::atomic::{AtomicI32, Ordering};
+///
+/// struct Slots {
+///     values: [u32; 2],
+///     current: AtomicSelfRef<u32, AtomicI32>,
+/// }
+///
+/// let mut slots = Slots { values: [1, 2], current: AtomicSelfRef::null() };
+/// let first = slots.current.offset_to(&mut slots.values[0]).unwrap();
+/// let second = slots.current.offset_to(&mut slots.values[1]).unwrap();
+/// slots.current.store(first, Ordering::Release);
+///
+/// let slots = Box::new(slots);
+/// assert!(slots.current.compare_exchange(first, second, Ordering::AcqRel, Ordering::Acquire).is_ok());
+///
+/// let base = &*slots as *const Slots as *const u8;
+/// let offset = slots.current.load(Ordering::Acquire);
+/// assert_eq!(unsafe { *slots.current.resolve(offset, base).unwrap().as_ptr() }, 2);
+/// ```
+///
+/// # Safety Considerations
+///
+/// As with `SelfRef`, offsets are only meaningful while the pointer and its target keep
+/// their relative positions. Moving the whole structure requires exclusive access, so no
+/// other thread can observe the atomic mid-move.
+pub struct AtomicSelfRef<T, A: AtomicOffset = AtomicIsize>(A, PhantomData<*mut T>);
+
+// Like `AtomicPtr`, the atomic only holds a distance; dereferencing it is `unsafe`.
+unsafe impl<T, A: AtomicOffset + Send> Send for AtomicSelfRef<T, A> {}
+unsafe impl<T, A: AtomicOffset + Sync> Sync for AtomicSelfRef<T, A> {}
+
+impl<T, A: AtomicOffset> fmt::Debug for AtomicSelfRef<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtomicSelfRef")
+            .field("ptr", &(self as *const Self))
+            .finish()
+    }
+}
+
+impl<T, A: AtomicOffset> Default for AtomicSelfRef<T, A> {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl<T, A: AtomicOffset> AtomicSelfRef<T, A> {
+    /// Creates an unset atomic pointer.
+    ///
+    /// # Returns
+    /// * `AtomicSelfRef<T, A>` - Pointer holding the null offset.
+    #[inline]
+    pub fn null() -> Self {
+        Self::from_offset(<A::Offset as Nullable>::NULL)
+    }
+
+    /// Creates an atomic pointer holding a previously computed offset.
+    ///
+    /// # Parameters
+    /// * `offset` - Distance from the pointer's final location to its target.
+    ///
+    /// # Returns
+    /// * `AtomicSelfRef<T, A>` - Pointer holding `offset`.
+    #[inline]
+    pub fn from_offset(offset: A::Offset) -> Self {
+        Self(A::new(offset), PhantomData)
+    }
+
+    /// Computes the offset from this pointer's current location to `target`.
+    ///
+    /// # Parameters
+    /// * `target` - Value the offset should point at.
+    ///
+    /// # Returns
+    /// * `Result<A::Offset, <A::Offset as Offset>::Error>` - Offset to store, or the conversion
+    ///   error when the distance does not fit.
+    #[inline]
+    pub fn offset_to(&self, target: *mut T) -> Result<A::Offset, <A::Offset as Offset>::Error> {
+        A::Offset::sub(target as *mut u8, self as *const Self as *mut u8)
+    }
+
+    /// Loads the stored offset.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> A::Offset {
+        self.0.load(order)
+    }
+
+    /// Stores a new offset.
+    #[inline]
+    pub fn store(&self, offset: A::Offset, order: Ordering) {
+        self.0.store(offset, order)
+    }
+
+    /// Stores a new offset, returning the previous one.
+    #[inline]
+    pub fn swap(&self, offset: A::Offset, order: Ordering) -> A::Offset {
+        self.0.swap(offset, order)
+    }
+
+    /// Stores `new` if the current offset equals `current`.
+    ///
+    /// # Returns
+    /// * `Result<A::Offset, A::Offset>` - Previous offset, `Ok` when the exchange happened.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: A::Offset,
+        new: A::Offset,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<A::Offset, A::Offset> {
+        self.0.compare_exchange(current, new, success, failure)
+    }
+
+    /// Like [`AtomicSelfRef::compare_exchange`], but allowed to fail spuriously.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: A::Offset,
+        new: A::Offset,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<A::Offset, A::Offset> {
+        self.0.compare_exchange_weak(current, new, success, failure)
+    }
+
+    /// Points the atomic at `target`.
+    ///
+    /// # Returns
+    /// * `Result<(), <A::Offset as Offset>::Error>` - `Ok` when the offset fits.
+    #[inline]
+    pub fn set(&self, target: *mut T, order: Ordering) -> Result<(), <A::Offset as Offset>::Error> {
+        let offset = self.offset_to(target)?;
+        self.store(offset, order);
+        Ok(())
+    }
+
+    /// Converts an offset loaded from this atomic into a pointer.
+    ///
+    /// # Safety
+    ///
+    /// * `base` must be the start address of the object that currently contains `self`.
+    /// * `offset` must have been measured from this atomic's position within that object.
+    ///
+    /// # Parameters
+    /// * `offset` - Offset previously loaded from this atomic.
+    /// * `base` - Address of the owning container currently holding the pointer.
+    ///
+    /// # Returns
+    /// * `Option<NonNull<T>>` - Target pointer, or `None` for the null offset.
+    #[inline]
+    pub unsafe fn resolve(&self, offset: A::Offset, base: *const u8) -> Option<NonNull<T>> {
+        resolve_from_base(self, offset, base)
+    }
+
+    /// Loads the offset and converts it into a pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`AtomicSelfRef::resolve`].
+    #[inline]
+    pub unsafe fn load_ptr(&self, base: *const u8, order: Ordering) -> Option<NonNull<T>> {
+        self.resolve(self.load(order), base)
+    }
+}
+
+/// An atomic relative pointer paired with a version stamp to resist ABA.
+///
+/// The 32-bit offset and a 32-bit stamp share one `AtomicU64`. Every successful
+/// store, swap or exchange bumps the stamp, so a compare-exchange only succeeds
+/// against the exact state that was loaded, even if the offset itself was changed
+/// away and back in the meantime.
+#[cfg(any(all(test, loom), feature = "portable-atomic", target_has_atomic = "64"))]
+pub struct AtomicTaggedSelfRef<T>(crate::sync::AtomicU64, PhantomData<*mut T>);
+
+#[cfg(any(all(test, loom), feature = "portable-atomic", target_has_atomic = "64"))]
+mod tagged {
+    use super::*;
+    use crate::sync::AtomicU64;
+
+    // Same reasoning as for `AtomicSelfRef`.
+    unsafe impl<T> Send for AtomicTaggedSelfRef<T> {}
+    unsafe impl<T> Sync for AtomicTaggedSelfRef<T> {}
+
+    #[inline]
+    fn pack(offset: i32, stamp: u32) -> u64 {
+        (u64::from(stamp) << 32) | u64::from(offset as u32)
+    }
+
+    #[inline]
+    fn unpack(raw: u64) -> (i32, u32) {
+        (raw as u32 as i32, (raw >> 32) as u32)
+    }
+
+    impl<T> fmt::Debug for AtomicTaggedSelfRef<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("AtomicTaggedSelfRef")
+                .field("ptr", &(self as *const Self))
+                .finish()
+        }
+    }
+
+    impl<T> Default for AtomicTaggedSelfRef<T> {
+        fn default() -> Self {
+            Self::null()
+        }
+    }
+
+    impl<T> AtomicTaggedSelfRef<T> {
+        /// Creates an unset pointer with a stamp of zero.
+        #[inline]
+        pub fn null() -> Self {
+            Self(AtomicU64::new(0), PhantomData)
+        }
+
+        /// Computes the offset from this pointer's current location to `target`.
+        ///
+        /// # Returns
+        /// * `Result<i32, IntegerOffsetError>` - Offset to store, or the conversion error.
+        #[inline]
+        pub fn offset_to(&self, target: *mut T) -> Result<i32, crate::IntegerOffsetError> {
+            i32::sub(target as *mut u8, self as *const Self as *mut u8)
+        }
+
+        /// Loads the stored offset together with its stamp.
+        ///
+        /// # Returns
+        /// * `(i32, u32)` - Current offset and stamp.
+        #[inline]
+        pub fn load(&self, order: Ordering) -> (i32, u32) {
+            unpack(self.0.load(order))
+        }
+
+        /// Stores a new offset and bumps the stamp.
+        #[inline]
+        pub fn store(&self, offset: i32, order: Ordering) {
+            self.swap(offset, order);
+        }
+
+        /// Stores a new offset and bumps the stamp, returning the previous state.
+        ///
+        /// # Returns
+        /// * `(i32, u32)` - Previous offset and stamp.
+        pub fn swap(&self, offset: i32, order: Ordering) -> (i32, u32) {
+            let mut current = self.0.load(Ordering::Relaxed);
+            loop {
+                let (_, stamp) = unpack(current);
+                let new = pack(offset, stamp.wrapping_add(1));
+                match self
+                    .0
+                    .compare_exchange_weak(current, new, order, Ordering::Relaxed)
+                {
+                    Ok(previous) => return unpack(previous),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+
+        /// Stores `new` if the pointer still holds exactly `current` (offset and stamp).
+        ///
+        /// On success the stamp is bumped past `current.1`.
+        ///
+        /// # Returns
+        /// * `Result<(i32, u32), (i32, u32)>` - Previous state, `Ok` when the exchange happened.
+        #[inline]
+        pub fn compare_exchange(
+            &self,
+            current: (i32, u32),
+            new: i32,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<(i32, u32), (i32, u32)> {
+            self.0
+                .compare_exchange(
+                    pack(current.0, current.1),
+                    pack(new, current.1.wrapping_add(1)),
+                    success,
+                    failure,
+                )
+                .map(unpack)
+                .map_err(unpack)
+        }
+
+        /// Converts an offset loaded from this atomic into a pointer.
+        ///
+        /// # Safety
+        ///
+        /// Same requirements as [`AtomicSelfRef::resolve`].
+        #[inline]
+        pub unsafe fn resolve(&self, offset: i32, base: *const u8) -> Option<NonNull<T>> {
+            resolve_from_base(self, offset, base)
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Pointer casts
+//!
+//! This module contains the type-changing conversions for the `SelfRef` type.
+
+use super::self_ref::SelfRef;
+use crate::error::{CastError, CastErrorImpl};
+use crate::metadata::PointerRecomposition;
+use crate::offset::{IntegerOffset, Offset};
+use core::any::Any;
+use core::mem::{align_of, size_of};
+
+#[inline]
+fn target_addr<T: ?Sized + PointerRecomposition, I: IntegerOffset>(
+    ptr: &SelfRef<T, I>,
+) -> Result<usize, CastError> {
+    ptr.target_addr().ok_or(CastError(CastErrorImpl::Range))
+}
+
+#[inline]
+fn check_alignment(addr: usize, align: usize) -> Result<(), CastError> {
+    if addr & (align - 1) == 0 {
+        Ok(())
+    } else {
+        Err(CastError(CastErrorImpl::Alignment(addr, align)))
+    }
+}
+
+/// Views a pointer as a pointer to another target type, in place.
+///
+/// # Safety
+///
+/// `T` and `U` must both be sized, or both be unsized with the same kind of metadata, so
+/// that `SelfRef<T, I>` and `SelfRef<U, I>` share their `#[repr(C)]` layout.
+#[inline]
+unsafe fn reinterpret<T, U, I>(ptr: &SelfRef<T, I>) -> &SelfRef<U, I>
+where
+    T: ?Sized + PointerRecomposition,
+    U: ?Sized + PointerRecomposition<Components = T::Components>,
+    I: Offset,
+{
+    &*(ptr as *const SelfRef<T, I> as *const SelfRef<U, I>)
+}
+
+impl<T: PointerRecomposition<Components = ()>, I: IntegerOffset> SelfRef<T, I> {
+    fn check_cast<U>(&self) -> Result<(), CastError> {
+        if size_of::<T>() != size_of::<U>() {
+            return Err(CastError(CastErrorImpl::Size(
+                size_of::<T>(),
+                size_of::<U>(),
+            )));
+        }
+        if self.is_ready() {
+            check_alignment(target_addr(self)?, align_of::<U>())?;
+        }
+        Ok(())
+    }
+
+    /// Views this pointer as a pointer to another sized type.
+    ///
+    /// The cast reinterprets `self` in place, so the returned pointer sits at the same
+    /// address, measures the same offset and keeps resolving after the owner moves. `T`
+    /// and `U` must have the same size, and the target as seen from this pointer's current
+    /// location must be aligned for `U`. When `U` needs stronger alignment than `T` the
+    /// owning structure must keep the target aligned across moves as well, for example
+    /// through `#[repr(align)]`.
+    ///
+    /// ```rust
+    /// use movable_ref::SelfRef;
+    /// #[repr(C, align(4))]
+    /// struct Packet {
+    ///     bytes: [u8; 4],
+    ///     ptr: SelfRef<[u8; 4], i8>,
+    /// }
+    /// let mut packet = Packet { bytes: 7u32.to_ne_bytes(), ptr: SelfRef::null() };
+    /// packet.ptr.set(&mut packet.bytes).unwrap();
+    ///
+    /// let packet = Box::new(packet);
+    /// let base = &*packet as *const Packet as *const u8;
+    /// let word = packet.ptr.cast::<u32>().unwrap();
+    /// assert_eq!(unsafe { *word.get_ref_from_base_unchecked(base) }, 7);
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<&SelfRef<U, I>, CastError>` - This pointer typed as a pointer to `U`, or the
+    ///   layout mismatch that prevented the cast. Unset pointers stay unset.
+    pub fn cast<U: PointerRecomposition<Components = ()>>(
+        &self,
+    ) -> Result<&SelfRef<U, I>, CastError> {
+        self.check_cast::<U>()?;
+        Ok(unsafe { reinterpret(self) })
+    }
+
+    /// Views this pointer as a mutable pointer to another sized type.
+    ///
+    /// Performs the same checks as [`SelfRef::cast`]. Retargeting through the result
+    /// leaves `self` pointing at a `U`.
+    ///
+    /// # Returns
+    /// * `Result<&mut SelfRef<U, I>, CastError>` - This pointer typed as a pointer to `U`, or
+    ///   the layout mismatch that prevented the cast. Unset pointers stay unset.
+    pub fn cast_mut<U: PointerRecomposition<Components = ()>>(
+        &mut self,
+    ) -> Result<&mut SelfRef<U, I>, CastError> {
+        self.check_cast::<U>()?;
+        Ok(unsafe { &mut *(self as *mut Self as *mut SelfRef<U, I>) })
+    }
+}
+
+impl<T, I: IntegerOffset> SelfRef<[T], I> {
+    /// Stores a pointer to the slice elements, reinterpreted as `U`, into `slot`.
+    ///
+    /// Changing the element type changes the stored length, so the result cannot reuse
+    /// `self` in place. Instead the target is located from this pointer's current position
+    /// and `slot` is set to it, with its offset measured from the slot's own address.
+    /// `slot` should live in the same owner as the target so that it keeps resolving after
+    /// the owner moves. The byte length of the slice must divide evenly into elements of
+    /// `U`, and the target must be aligned for `U`.
+    ///
+    /// # Parameters
+    /// * `slot` - Pointer to overwrite; it is reset when `self` is unset.
+    ///
+    /// # Returns
+    /// * `Result<(), CastError>` - `Ok` once `slot` points at the elements, or the layout
+    ///   mismatch or offset overflow that prevented the cast. `slot` is untouched on error.
+    pub fn cast_slice_into<U>(&self, slot: &mut SelfRef<[U], I>) -> Result<(), CastError> {
+        let len = match self.components_if_ready() {
+            Some(len) => len,
+            None => {
+                slot.reset();
+                return Ok(());
+            }
+        };
+        let bytes = len * size_of::<T>();
+        if bytes.checked_rem(size_of::<U>()) != Some(0) {
+            return Err(CastError(CastErrorImpl::Size(bytes, size_of::<U>())));
+        }
+        let target = target_addr(self)?;
+        check_alignment(target, align_of::<U>())?;
+        let offset = I::sub(target as *mut u8, slot as *mut SelfRef<[U], I> as *mut u8)
+            .map_err(|err| CastError(CastErrorImpl::Offset(err)))?;
+        *slot = SelfRef::from_parts(offset, bytes / size_of::<U>());
+        Ok(())
+    }
+}
+
+impl<I: Offset> SelfRef<[u8], I> {
+    /// Views this byte slice pointer as a string pointer after validating its contents.
+    ///
+    /// The cast reinterprets `self` in place. Validation happens against the bytes
+    /// resolved at call time; the returned pointer trusts that later writes through the
+    /// owner keep them UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`SelfRef::get_ref_from_base_unchecked`], except that the
+    /// pointer may be unset.
+    ///
+    /// # Parameters
+    /// * `base` - Address of the owning container currently holding the pointer.
+    ///
+    /// # Returns
+    /// * `Result<&SelfRef<str, I>, CastError>` - This pointer typed as a pointer to `str`,
+    ///   or the UTF-8 error found in the target.
+    pub unsafe fn try_cast_str(&self, base: *const u8) -> Result<&SelfRef<str, I>, CastError> {
+        if !self.is_ready() {
+            return Err(CastError(CastErrorImpl::Unset));
+        }
+        let bytes = self.get_ref_from_base_unchecked(base);
+        core::str::from_utf8(bytes).map_err(|err| CastError(CastErrorImpl::Utf8(err)))?;
+        Ok(reinterpret(self))
+    }
+}
+
+impl<I: Offset> SelfRef<str, I> {
+    /// Views this string pointer as a pointer to its underlying bytes, in place.
+    ///
+    /// # Returns
+    /// * `&SelfRef<[u8], I>` - This pointer typed as a pointer to `[u8]`.
+    #[inline]
+    pub fn cast_bytes(&self) -> &SelfRef<[u8], I> {
+        unsafe { reinterpret(self) }
+    }
+}
+
+macro_rules! impl_downcast {
+    ($($any:ty),* $(,)?) => {$(
+        impl<I: Offset> SelfRef<$any, I> {
+            /// Resolves the target relative to `base` and downcasts it to `T`.
+            ///
+            /// # Safety
+            ///
+            /// Same requirements as [`SelfRef::get_ref_from_base_unchecked`].
+            ///
+            /// # Parameters
+            /// * `base` - Address of the owning container currently holding the pointer.
+            ///
+            /// # Returns
+            /// * `Option<&'a T>` - Shared reference when the target is a `T`.
+            #[inline]
+            pub unsafe fn downcast_ref_from_base<'a, T: Any>(&self, base: *const u8) -> Option<&'a T> {
+                self.get_ref_from_base_unchecked(base).downcast_ref()
+            }
+
+            /// Resolves the target relative to `base` and downcasts it to `T` mutably.
+            ///
+            /// # Safety
+            ///
+            /// Same requirements as [`SelfRef::get_mut_from_base_unchecked`].
+            ///
+            /// # Parameters
+            /// * `base` - Address of the owning container currently holding the pointer.
+            ///
+            /// # Returns
+            /// * `Option<&'a mut T>` - Exclusive reference when the target is a `T`.
+            #[inline]
+            pub unsafe fn downcast_mut_from_base<'a, T: Any>(&self, base: *mut u8) -> Option<&'a mut T> {
+                self.get_mut_from_base_unchecked(base).downcast_mut()
+            }
+        }
+    )*};
+}
+
+impl_downcast!(dyn Any, dyn Any + Send, dyn Any + Send + Sync);
@@ -0,0 +1,161 @@
+//! FieldRef type definition
+//!
+//! This module contains a zero-sized reference to a field at an offset fixed
+//! at compile time.
+
+use crate::metadata::PointerRecomposition;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{forget, size_of};
+use core::ptr::NonNull;
+
+/// A zero-sized reference to the field of `Owner` that starts `OFFSET` bytes into it.
+///
+/// When the target's position is fixed by the owner's layout there is nothing to store
+/// at runtime: the field is found by adding `OFFSET` to the owner's address. `FieldRef`
+/// carries the offset in its type, costs no space, and resolves against any owner it is
+/// handed, wherever that owner has moved.
+///
+/// The [`field_ref!`](crate::field_ref) macro computes the offset with
+/// `core::mem::offset_of!`:
+///
+/// ```rust
+/// use core::mem::{offset_of, size_of};
+/// use movable_ref::{field_ref, FieldRef};
+///
+/// struct Header {
+///     id: u16,
+///     len: u32,
+/// }
+///
+/// const LEN: FieldRef<Header, u32, { offset_of!(Header, len) }> = field_ref!(Header, len);
+/// assert_eq!(size_of::<FieldRef<Header, u32, 4>>(), 0);
+///
+/// let mut header = Box::new(Header { id: 1, len: 64 });
+/// *LEN.get_mut(&mut header) += 1;
+/// assert_eq!(*LEN.get(&header), 65);
+/// ```
+///
+/// For an owner ending in an unsized field, [`FieldRef::get_tail`] rebuilds the field
+/// from the owner's own metadata through [`PointerRecomposition`].
+pub struct FieldRef<Owner: ?Sized, T: ?Sized, const OFFSET: usize>(PhantomData<fn(&Owner) -> &T>);
+
+impl<Owner: ?Sized, T: ?Sized, const OFFSET: usize> FieldRef<Owner, T, OFFSET> {
+    /// Creates a field reference from its byte offset.
+    ///
+    /// # Returns
+    /// * `FieldRef<Owner, T, OFFSET>` - Zero-sized reference to the field.
+    ///
+    /// # Safety
+    ///
+    /// Every `Owner` must hold a valid, suitably aligned `T` starting `OFFSET` bytes into
+    /// it. For an unsized `T`, that field must be `Owner`'s unsized tail.
+    #[inline]
+    pub const unsafe fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Creates a field reference, taking the field's type from an accessor.
+    ///
+    /// Used by [`field_ref!`](crate::field_ref); the accessor is never called.
+    #[doc(hidden)]
+    #[inline]
+    pub const unsafe fn __with_accessor<F>(accessor: F) -> Self
+    where
+        F: FnOnce(&Owner) -> &T,
+    {
+        forget(accessor);
+        Self(PhantomData)
+    }
+
+    /// Byte offset of the field inside its owner.
+    ///
+    /// # Returns
+    /// * `usize` - The `OFFSET` parameter.
+    #[inline]
+    pub const fn offset(self) -> usize {
+        OFFSET
+    }
+}
+
+impl<Owner, T, const OFFSET: usize> FieldRef<Owner, T, OFFSET> {
+    /// Resolves the field inside `owner`.
+    ///
+    /// # Parameters
+    /// * `owner` - Value holding the field.
+    ///
+    /// # Returns
+    /// * `&T` - Shared reference to the field.
+    #[inline]
+    pub fn get(self, owner: &Owner) -> &T {
+        debug_assert!(OFFSET + size_of::<T>() <= size_of::<Owner>());
+        unsafe { &*((owner as *const Owner as *const u8).add(OFFSET) as *const T) }
+    }
+
+    /// Resolves the field inside `owner` for mutation.
+    ///
+    /// # Parameters
+    /// * `owner` - Value holding the field.
+    ///
+    /// # Returns
+    /// * `&mut T` - Exclusive reference to the field.
+    #[inline]
+    pub fn get_mut(self, owner: &mut Owner) -> &mut T {
+        debug_assert!(OFFSET + size_of::<T>() <= size_of::<Owner>());
+        unsafe { &mut *((owner as *mut Owner as *mut u8).add(OFFSET) as *mut T) }
+    }
+}
+
+impl<Owner, T, const OFFSET: usize> FieldRef<Owner, T, OFFSET>
+where
+    Owner: ?Sized + PointerRecomposition,
+    T: ?Sized + PointerRecomposition<Components = Owner::Components>,
+{
+    /// Resolves the unsized tail of `owner`, reusing the owner's metadata for the field.
+    ///
+    /// # Parameters
+    /// * `owner` - Value ending in the field.
+    ///
+    /// # Returns
+    /// * `&T` - Shared reference to the field.
+    #[inline]
+    pub fn get_tail(self, owner: &Owner) -> &T {
+        let components = Owner::decompose(owner);
+        let data =
+            unsafe { NonNull::new_unchecked((owner as *const Owner as *mut u8).add(OFFSET)) };
+        match unsafe { T::recompose(Some(data), components) } {
+            Some(ptr) => unsafe { &*ptr.as_ptr() },
+            None => unreachable!("field pointer is not null"),
+        }
+    }
+
+    /// Resolves the unsized tail of `owner` for mutation.
+    ///
+    /// # Parameters
+    /// * `owner` - Value ending in the field.
+    ///
+    /// # Returns
+    /// * `&mut T` - Exclusive reference to the field.
+    #[inline]
+    pub fn get_tail_mut(self, owner: &mut Owner) -> &mut T {
+        let components = Owner::decompose(owner);
+        let data = unsafe { NonNull::new_unchecked((owner as *mut Owner as *mut u8).add(OFFSET)) };
+        match unsafe { T::recompose(Some(data), components) } {
+            Some(ptr) => unsafe { &mut *ptr.as_ptr() },
+            None => unreachable!("field pointer is not null"),
+        }
+    }
+}
+
+impl<Owner: ?Sized, T: ?Sized, const OFFSET: usize> Copy for FieldRef<Owner, T, OFFSET> {}
+impl<Owner: ?Sized, T: ?Sized, const OFFSET: usize> Clone for FieldRef<Owner, T, OFFSET> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Owner: ?Sized, T: ?Sized, const OFFSET: usize> fmt::Debug for FieldRef<Owner, T, OFFSET> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FieldRef").field(&OFFSET).finish()
+    }
+}
@@ -3,9 +3,26 @@
 //! This module contains the main `SelfRef` type and all operations
 //! related to relative pointer manipulation.
 
+#[cfg(any(
+    all(test, loom),
+    feature = "portable-atomic",
+    target_has_atomic = "ptr"
+))]
+mod atomic;
+mod cast;
+mod field_ref;
 mod operations;
 mod self_ref;
+mod tagged;
 /// Module for handling unreachable code
 pub mod unreachable;
 
+#[cfg(any(
+    all(test, loom),
+    feature = "portable-atomic",
+    target_has_atomic = "ptr"
+))]
+pub use atomic::*;
+pub use field_ref::FieldRef;
 pub use self_ref::SelfRef;
+pub use tagged::TaggedSelfRef;
@@ -20,3 +20,24 @@ impl<T: ?Sized + PointerRecomposition, I: Debug + Offset> Debug for SelfRef<T, I
             .finish()
     }
 }
+
+macro_rules! impl_widening_from {
+    ($($from:ty => $($to:ty),+;)*) => {$($(
+        impl<T: ?Sized + PointerRecomposition> From<SelfRef<T, $from>> for SelfRef<T, $to> {
+            #[inline]
+            fn from(ptr: SelfRef<T, $from>) -> Self {
+                match ptr.try_convert() {
+                    Ok(ptr) => ptr,
+                    Err(_) => unreachable!("widening an offset cannot overflow"),
+                }
+            }
+        }
+    )+)*};
+}
+
+impl_widening_from! {
+    i8 => i16, i32, i64, i128, isize;
+    i16 => i32, i64, i128, isize;
+    i32 => i64, i128;
+    i64 => i128;
+}
@@ -2,17 +2,23 @@
 //!
 //! This module contains the main `SelfRef` type that represents a relative pointer.
 
+use crate::error::IntegerOffsetError;
 use crate::metadata::PointerRecomposition;
-use crate::offset::{Nullable, Offset, Ptr};
+use crate::offset::{IntegerOffset, Nullable, Offset, Ptr};
 use crate::pointer::unreachable::UncheckedOptionExt as _;
 use core::marker::PhantomData;
 use core::mem::MaybeUninit;
 use std::ptr::NonNull;
 
+// Without `debug-guards` the payload is zero-sized, so a `SelfRef` only costs its offset,
+// its components and the readiness flag.
+#[cfg(feature = "debug-guards")]
 type GuardPayload<T> = Option<NonNull<T>>;
+#[cfg(not(feature = "debug-guards"))]
+type GuardPayload<T> = PhantomData<NonNull<T>>;
 
 #[inline]
-fn guard_payload_from<T: ?Sized>(target: Option<NonNull<T>>) -> GuardPayload<T> {
+const fn guard_payload_from<T: ?Sized>(target: Option<NonNull<T>>) -> GuardPayload<T> {
     #[cfg(feature = "debug-guards")]
     {
         target
@@ -20,12 +26,12 @@ fn guard_payload_from<T: ?Sized>(target: Option<NonNull<T>>) -> GuardPayload<T>
     #[cfg(not(feature = "debug-guards"))]
     {
         let _ = target;
-        None
+        PhantomData
     }
 }
 
 #[inline]
-fn guard_payload_empty<T: ?Sized>() -> GuardPayload<T> {
+const fn guard_payload_empty<T: ?Sized>() -> GuardPayload<T> {
     guard_payload_from::<T>(None)
 }
 
@@ -56,6 +62,9 @@ fn guard_assert_target<T: ?Sized>(payload: GuardPayload<T>, target: *mut u8) {
     }
 }
 
+// `repr(u8)` fixes the tag at one byte, `0` for unset and `1` for ready, followed by the
+// guard payload.
+#[repr(u8)]
 enum RefState<T: ?Sized> {
     Unset,
     Ready(GuardPayload<T>),
@@ -120,11 +129,30 @@ fn nn_to_ptr<T: ?Sized>(nn: Ptr<T>) -> *mut T {
 /// the entire structure is always safe - it's only internal layout changes that cause issues.
 ///
 /// Special care needed with packed structs: field reordering during drops can invalidate offsets.
+///
+/// # Thread Safety
+///
+/// `SelfRef<T, I>` is `Send` when `T` is `Send` and `Sync` when `T` is `Sync`, the same rules
+/// as `&mut T`. The stored offset is a distance between two places inside one owner, not an
+/// address, so sending the owner to another thread is just another move: the pointer and its
+/// target travel together and their distance is unchanged. Nothing about the offset depends
+/// on the thread that computed it. Absolute targets recorded for `debug-guards` go stale on a
+/// cross-thread move exactly as they do on any other move.
+///
+/// # Layout
+///
+/// `SelfRef` is `#[repr(C)]`. It starts with a state byte that is `0` while unset and `1`
+/// once ready, followed by the offset `I` and then `T::Components`, which are
+/// uninitialised while the pointer is unset. With a fixed-width offset and compact
+/// metadata such as [`Len16`](crate::Len16), a pointer therefore has the same size and
+/// field offsets on every host, with integers in native byte order. `debug-guards` stores
+/// the recorded target between the state byte and the offset.
+#[repr(C)]
 pub struct SelfRef<T: ?Sized + PointerRecomposition, I: Offset = isize>(
+    RefState<T>,
     I,
     MaybeUninit<T::Components>,
     PhantomData<*mut T>,
-    RefState<T>,
 );
 
 // Ergonomics and ptr like impls
@@ -136,12 +164,25 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> Clone for SelfRef<T, I> {
     }
 }
 
+// SAFETY: The raw pointers in `SelfRef` (the `PhantomData<*mut T>` marker and the
+// `debug-guards` target) are never dereferenced without the caller upholding the
+// `unsafe` accessor contracts, and the offset is relative, so moving or sharing a
+// `SelfRef` across threads is equivalent to moving or sharing `&mut T`.
+unsafe impl<T: ?Sized + PointerRecomposition + Send, I: Offset + Send> Send for SelfRef<T, I> where
+    T::Components: Send
+{
+}
+unsafe impl<T: ?Sized + PointerRecomposition + Sync, I: Offset + Sync> Sync for SelfRef<T, I> where
+    T::Components: Sync
+{
+}
+
 impl<T: ?Sized + PointerRecomposition, I: Offset> Eq for SelfRef<T, I> {}
 impl<T: ?Sized + PointerRecomposition, I: Offset> PartialEq for SelfRef<T, I> {
     fn eq(&self, other: &Self) -> bool {
         match (self.components_if_ready(), other.components_if_ready()) {
             (None, None) => true,
-            (Some(lhs), Some(rhs)) => self.0 == other.0 && lhs == rhs,
+            (Some(lhs), Some(rhs)) => self.1 == other.1 && lhs == rhs,
             _ => false,
         }
     }
@@ -156,17 +197,42 @@ impl<T: ?Sized + PointerRecomposition, I: Nullable> SelfRef<T, I> {
     /// # Returns
     /// * `SelfRef<T, I>` - Pointer that must be initialised before use.
     #[inline(always)]
-    pub fn null() -> Self {
-        Self(I::NULL, MaybeUninit::uninit(), PhantomData, RefState::Unset)
+    pub const fn null() -> Self {
+        Self(RefState::Unset, I::NULL, MaybeUninit::uninit(), PhantomData)
     }
 
-    /// Checks if the pointer is unset.
+    /// Checks if the offset is null.
+    ///
+    /// An unset pointer has a null offset, but so does a ready one that targets itself;
+    /// use [`SelfRef::is_ready`] to tell whether the pointer has been initialised.
     ///
     /// # Returns
-    /// * `bool` - `true` when the pointer has not been initialised.
+    /// * `bool` - `true` when the stored offset is zero.
     #[inline(always)]
     pub fn is_null(&self) -> bool {
-        self.0 == I::NULL
+        self.1 == I::NULL
+    }
+
+    /// Returns the pointer to the unset state.
+    ///
+    /// Any absolute target recorded for `debug-guards` is discarded along with the
+    /// offset and metadata, so a later `set` starts from a clean slate.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.1 = I::NULL;
+        self.2 = MaybeUninit::uninit();
+        self.0 = RefState::Unset;
+    }
+
+    /// Clears the pointer and hands back the parts it held.
+    ///
+    /// # Returns
+    /// * `Option<(I, T::Components)>` - Offset and metadata if the pointer was ready.
+    #[inline]
+    pub fn take(&mut self) -> Option<(I, T::Components)> {
+        let parts = self.parts_if_ready();
+        self.reset();
+        parts
     }
 }
 
@@ -176,8 +242,8 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// # Returns
     /// * `bool` - `true` when initialisation has completed.
     #[inline]
-    pub fn is_ready(&self) -> bool {
-        matches!(self.3, RefState::Ready(_))
+    pub const fn is_ready(&self) -> bool {
+        matches!(self.0, RefState::Ready(_))
     }
 
     /// Provides the stored metadata when the pointer is initialised.
@@ -186,7 +252,7 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// * `Option<T::Components>` - Metadata captured during initialisation.
     #[inline]
     pub fn components_if_ready(&self) -> Option<T::Components> {
-        match self.3 {
+        match self.0 {
             RefState::Ready(_) => Some(unsafe { self.components_unchecked() }),
             RefState::Unset => None,
         }
@@ -194,7 +260,7 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
 
     #[inline]
     unsafe fn components_unchecked(&self) -> T::Components {
-        *self.1.assume_init_ref()
+        *self.2.assume_init_ref()
     }
 
     /// Returns the raw distance recorded for this pointer.
@@ -203,7 +269,7 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// * `I` - Offset measured from this pointer to the target.
     #[inline]
     pub fn offset(&self) -> I {
-        self.0
+        self.1
     }
 
     /// Reconstructs a relative pointer from previously captured parts.
@@ -215,12 +281,12 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// # Returns
     /// * `SelfRef<T, I>` - Pointer ready to be used at the current location.
     #[inline]
-    pub fn from_parts(offset: I, components: T::Components) -> Self {
+    pub const fn from_parts(offset: I, components: T::Components) -> Self {
         Self(
+            RefState::Ready(guard_payload_empty::<T>()),
             offset,
             MaybeUninit::new(components),
             PhantomData,
-            RefState::Ready(guard_payload_empty::<T>()),
         )
     }
 
@@ -238,16 +304,16 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// # Returns
     /// * `SelfRef<T, I>` - Pointer configured with optional debug metadata.
     #[inline]
-    pub fn from_parts_with_target(
+    pub const fn from_parts_with_target(
         offset: I,
         components: T::Components,
         target: Option<NonNull<T>>,
     ) -> Self {
         Self(
+            RefState::Ready(guard_payload_from::<T>(target)),
             offset,
             MaybeUninit::new(components),
             PhantomData,
-            RefState::Ready(guard_payload_from::<T>(target)),
         )
     }
 
@@ -258,7 +324,7 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     #[inline]
     pub fn parts_if_ready(&self) -> Option<(I, T::Components)> {
         self.components_if_ready()
-            .map(|components| (self.0, components))
+            .map(|components| (self.1, components))
     }
 
     /// Returns offset, metadata, and any recorded absolute pointer when initialised.
@@ -268,8 +334,8 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     ///   along with the optional debug target.
     #[inline]
     pub fn parts_with_target_if_ready(&self) -> Option<(I, T::Components, Option<NonNull<T>>)> {
-        self.components_if_ready().map(|components| match self.3 {
-            RefState::Ready(payload) => (self.0, components, guard_extract_target::<T>(payload)),
+        self.components_if_ready().map(|components| match self.0 {
+            RefState::Ready(payload) => (self.1, components, guard_extract_target::<T>(payload)),
             RefState::Unset => unreachable!(),
         })
     }
@@ -296,13 +362,60 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     /// * `Result<(), I::Error>` - `Ok` when the offset fits in `I`, otherwise the conversion error.
     #[inline]
     pub fn set(&mut self, value: &mut T) -> Result<(), I::Error> {
-        self.0 = I::sub(value as *mut T as _, self as *mut Self as _)?;
-        self.1 = MaybeUninit::new(T::decompose(value));
-        self.3 = RefState::Ready(guard_payload_empty::<T>());
+        self.1 = I::sub(value as *mut T as _, self as *mut Self as _)?;
+        self.2 = MaybeUninit::new(T::decompose(value));
+        self.0 = RefState::Ready(guard_payload_empty::<T>());
 
         Ok(())
     }
 
+    /// Points the pointer at a new target and returns the previous parts.
+    ///
+    /// On error the pointer is left untouched.
+    ///
+    /// # Parameters
+    /// * `value` - New target to be referenced by the pointer.
+    ///
+    /// # Returns
+    /// * `Result<Option<(I, T::Components)>, I::Error>` - Previous offset and metadata (if any),
+    ///   or the conversion error when the new distance does not fit in `I`.
+    #[inline]
+    pub fn retarget(&mut self, value: &mut T) -> Result<Option<(I, T::Components)>, I::Error> {
+        let previous = self.parts_if_ready();
+        self.set(value)?;
+        Ok(previous)
+    }
+
+    /// Replaces the stored offset and metadata, returning the previous parts.
+    ///
+    /// Any absolute target recorded for `debug-guards` is cleared, since it described
+    /// the old target rather than the new one.
+    ///
+    /// # Parameters
+    /// * `offset` - Relative distance between pointer and the new target.
+    /// * `components` - Metadata produced by [`PointerRecomposition::decompose`].
+    ///
+    /// # Returns
+    /// * `Option<(I, T::Components)>` - Previous offset and metadata if the pointer was ready.
+    #[inline]
+    pub fn replace_parts(
+        &mut self,
+        offset: I,
+        components: T::Components,
+    ) -> Option<(I, T::Components)> {
+        let previous = self.parts_if_ready();
+        self.1 = offset;
+        self.2 = MaybeUninit::new(components);
+        self.0 = RefState::Ready(guard_payload_empty::<T>());
+        previous
+    }
+
+    /// Replaces the raw offset, keeping the metadata and readiness untouched.
+    #[inline]
+    pub(crate) fn with_offset(self, offset: I) -> Self {
+        SelfRef(self.0, offset, self.2, PhantomData)
+    }
+
     /// Sets the pointer without bounds checking.
     ///
     /// Like `set()` but assumes the offset will fit in type `I`. Used when you've
@@ -318,9 +431,9 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     #[inline]
     pub unsafe fn set_unchecked(&mut self, value: *mut T) {
         debug_assert!(!value.is_null());
-        self.0 = I::sub_unchecked(value as _, self as *mut Self as _);
-        self.1 = MaybeUninit::new(T::decompose(&*value));
-        self.3 = RefState::Ready(guard_payload_empty::<T>());
+        self.1 = I::sub_unchecked(value as _, self as *mut Self as _);
+        self.2 = MaybeUninit::new(T::decompose(&*value));
+        self.0 = RefState::Ready(guard_payload_empty::<T>());
     }
 
     /// Reconstructs the target pointer without null checking.
@@ -336,7 +449,7 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     unsafe fn as_raw_unchecked_impl(&mut self) -> *mut T {
         debug_assert!(self.is_ready());
         let base = self as *mut Self as *const u8;
-        let target = self.0.add(base);
+        let target = self.1.add(base);
         let components = unsafe { self.components_unchecked() };
         nn_to_ptr(T::recompose(NonNull::new(target), components))
     }
@@ -366,9 +479,9 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     pub unsafe fn as_non_null_unchecked(&mut self) -> NonNull<T> {
         debug_assert!(self.is_ready());
         let base = self as *mut Self as *const u8;
-        let target = self.0.add(base);
+        let target = self.1.add(base);
         let components = unsafe { self.components_unchecked() };
-        if let RefState::Ready(payload) = self.3 {
+        if let RefState::Ready(payload) = self.0 {
             guard_assert_target::<T>(payload, target);
         }
         T::recompose(NonNull::new(target), components)
@@ -411,8 +524,8 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
         let d_self = self_ptr.offset_from(base);
         let at_self = base.wrapping_offset(d_self);
         let components = unsafe { self.components_unchecked() };
-        let target = self.0.add(at_self);
-        if let RefState::Ready(payload) = self.3 {
+        let target = self.1.add(at_self);
+        if let RefState::Ready(payload) = self.0 {
             guard_assert_target::<T>(payload, target);
         }
         let p = nn_to_ptr(T::recompose(NonNull::new(target), components));
@@ -442,8 +555,8 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
         let d_self = self_ptr.offset_from(base_ptr);
         let at_self = base_ptr.wrapping_offset(d_self);
         let components = unsafe { self.components_unchecked() };
-        let target = self.0.add(at_self);
-        if let RefState::Ready(payload) = self.3 {
+        let target = self.1.add(at_self);
+        if let RefState::Ready(payload) = self.0 {
             guard_assert_target::<T>(payload, target);
         }
         let p = nn_to_ptr(T::recompose(NonNull::new(target), components));
@@ -464,6 +577,44 @@ impl<T: ?Sized + PointerRecomposition, I: Offset> SelfRef<T, I> {
     }
 }
 
+impl<T: ?Sized + PointerRecomposition, I: IntegerOffset> SelfRef<T, I> {
+    /// Re-encodes the stored offset in a different integer width.
+    ///
+    /// The converted pointer measures the same distance, so it is only valid once stored
+    /// at the same address as `self` (for example when a field is swapped for a wider one
+    /// in place). When the pointer is being copied into a different layout, the anchor
+    /// changes as well; decompose it with [`SelfRef::parts_if_ready`] and rebuild it with
+    /// [`SelfRef::from_parts`] using the distance measured at the new location instead.
+    ///
+    /// ```rust
+    /// use movable_ref::SelfRef;
+    /// let mut data = [0u8; 4];
+    /// let mut ptr: SelfRef<[u8; 4], i32> = SelfRef::null();
+    /// ptr.set(&mut data).unwrap();
+    /// let wide: SelfRef<[u8; 4], i64> = ptr.try_convert().unwrap();
+    /// assert_eq!(wide.offset() as isize, ptr.offset() as isize);
+    /// ```
+    ///
+    /// # Returns
+    /// * `Result<SelfRef<T, J>, IntegerOffsetError>` - Pointer with the offset stored as `J`,
+    ///   or the conversion error when the distance does not fit.
+    #[inline]
+    pub fn try_convert<J: IntegerOffset>(self) -> Result<SelfRef<T, J>, IntegerOffsetError> {
+        let offset = J::try_from_isize(self.1.try_into_isize()?)?;
+        Ok(SelfRef(self.0, offset, self.2, PhantomData))
+    }
+
+    /// Absolute address of the target as seen from this pointer's current location.
+    ///
+    /// Only address arithmetic is performed, the target is never dereferenced. Returns
+    /// `None` when the offset does not fit in `isize` and so names no address.
+    #[inline]
+    pub(crate) fn target_addr(&self) -> Option<usize> {
+        let del = self.1.try_into_isize().ok()?;
+        Some((self as *const Self as usize).wrapping_add(del as usize))
+    }
+}
+
 impl<T: ?Sized + PointerRecomposition, I: Nullable> SelfRef<T, I> {
     /// Reconstructs the target as a raw pointer, returning null if unset.
     ///
@@ -494,9 +645,9 @@ impl<T: ?Sized + PointerRecomposition, I: Nullable> SelfRef<T, I> {
             return None;
         }
         let base = self as *mut Self as *const u8;
-        let target = self.0.add(base);
+        let target = self.1.add(base);
         let components = unsafe { self.components_unchecked() };
-        if let RefState::Ready(payload) = self.3 {
+        if let RefState::Ready(payload) = self.0 {
             guard_assert_target::<T>(payload, target);
         }
         T::recompose(NonNull::new(target), components)
@@ -531,3 +682,43 @@ impl<T: ?Sized + PointerRecomposition, I: Nullable> SelfRef<T, I> {
             .map(|mut_ptr| unsafe { &mut *mut_ptr.as_ptr() })
     }
 }
+
+macro_rules! impl_const_offsets {
+    ($($type:ty),* $(,)?) => {$(
+        impl<T: ?Sized + PointerRecomposition> SelfRef<T, $type> {
+            /// Builds a ready pointer from the positions of the pointer and its target
+            /// inside a common owner, for use in `const` and `static` items.
+            ///
+            /// Positions are byte offsets from the start of the owner, such as those
+            /// returned by `core::mem::offset_of!`.
+            ///
+            /// # Parameters
+            /// * `pointer` - Offset of this `SelfRef` inside the owner.
+            /// * `target` - Offset of the target inside the owner.
+            /// * `components` - Metadata of the target.
+            ///
+            /// # Returns
+            /// * `SelfRef<T, I>` - Pointer that resolves once placed at `pointer`.
+            ///
+            /// # Panics
+            ///
+            /// Panics, or fails to compile in a constant, when the distance does not fit.
+            #[inline]
+            pub const fn from_field_offsets(
+                pointer: usize,
+                target: usize,
+                components: T::Components,
+            ) -> Self {
+                let distance = target as isize - pointer as isize;
+                assert!(
+                    distance >= <$type as IntegerOffset>::MIN_DISTANCE
+                        && distance <= <$type as IntegerOffset>::MAX_DISTANCE,
+                    "distance does not fit in the offset type"
+                );
+                Self::from_parts(distance as $type, components)
+            }
+        }
+    )*};
+}
+
+impl_const_offsets! { i8, i16, i32, i64, i128, isize }
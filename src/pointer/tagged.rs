@@ -0,0 +1,250 @@
+//! TaggedSelfRef type definition
+//!
+//! This module contains a relative pointer that packs a small tag into the
+//! low bits of its offset.
+
+use super::self_ref::SelfRef;
+use super::unreachable::UncheckedOptionExt as _;
+use crate::error::{IntegerOffsetError, IntegerOffsetErrorImpl};
+use crate::metadata::PointerRecomposition;
+use crate::offset::{IntegerOffset, Nullable, Offset};
+use core::fmt;
+use core::mem::size_of;
+use std::ptr::NonNull;
+
+/// A relative pointer that stores a small tag in the spare low bits of its offset.
+///
+/// When both the pointer and its target sit at addresses aligned to `1 << BITS`,
+/// the low `BITS` bits of the offset are always zero. `TaggedSelfRef` uses them to
+/// carry a tag such as an enum discriminant or a mark bit, without growing the
+/// pointer. The tag is masked off whenever the target is resolved.
+///
+/// Because the offset is a distance, the alignment check done by [`TaggedSelfRef::set`]
+/// stays valid when the owning structure moves.
+///
+/// ```rust
+/// use movable_ref::TaggedSelfRef;
+///
+/// #[repr(C, align(4))]
+/// struct Slots {
+///     current: TaggedSelfRef<u32, i16, 2>,
+///     values: [u32; 2],
+/// }
+///
+/// let mut slots = Slots { current: TaggedSelfRef::null(), values: [10, 20] };
+/// slots.current.set(&mut slots.values[1]).unwrap();
+/// slots.current.set_tag(3);
+///
+/// let slots = Box::new(slots);
+/// let base = &*slots as *const Slots as *const u8;
+/// assert_eq!(slots.current.tag(), 3);
+/// assert_eq!(unsafe { *slots.current.get_ref_from_base_unchecked(base) }, 20);
+/// ```
+#[repr(transparent)]
+pub struct TaggedSelfRef<T: ?Sized + PointerRecomposition, I: Offset, const BITS: u32>(
+    SelfRef<T, I>,
+);
+
+impl<T: ?Sized + PointerRecomposition, I: Offset, const BITS: u32> Copy
+    for TaggedSelfRef<T, I, BITS>
+{
+}
+impl<T: ?Sized + PointerRecomposition, I: Offset, const BITS: u32> Clone
+    for TaggedSelfRef<T, I, BITS>
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized + PointerRecomposition, I: Offset, const BITS: u32> Eq
+    for TaggedSelfRef<T, I, BITS>
+{
+}
+impl<T: ?Sized + PointerRecomposition, I: Offset, const BITS: u32> PartialEq
+    for TaggedSelfRef<T, I, BITS>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: ?Sized + PointerRecomposition, I: IntegerOffset, const BITS: u32> fmt::Debug
+    for TaggedSelfRef<T, I, BITS>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedSelfRef")
+            .field("ptr", &(self as *const Self))
+            .field("tag", &self.tag())
+            .finish()
+    }
+}
+
+impl<T: ?Sized + PointerRecomposition, I: Nullable, const BITS: u32> TaggedSelfRef<T, I, BITS> {
+    /// Creates an unset tagged pointer with a tag of zero.
+    ///
+    /// # Returns
+    /// * `TaggedSelfRef<T, I, BITS>` - Pointer that must be initialised before use.
+    #[inline]
+    pub fn null() -> Self {
+        Self(SelfRef::null())
+    }
+}
+
+impl<T: ?Sized + PointerRecomposition, I: IntegerOffset, const BITS: u32>
+    TaggedSelfRef<T, I, BITS>
+{
+    /// Mask selecting the tag bits of the stored offset.
+    pub const TAG_MASK: usize = {
+        assert!(
+            (BITS as usize) < 8 * size_of::<I>(),
+            "tag bits must leave room for the offset"
+        );
+        (1 << BITS) - 1
+    };
+
+    /// Returns `true` once the pointer has been set.
+    ///
+    /// # Returns
+    /// * `bool` - `true` when initialisation has completed.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.is_ready()
+    }
+
+    /// Returns the tag stored in the low bits of the offset.
+    ///
+    /// # Returns
+    /// * `usize` - Tag in the range `0..=TAG_MASK`.
+    #[inline]
+    pub fn tag(&self) -> usize {
+        self.raw() as usize & Self::TAG_MASK
+    }
+
+    /// Replaces the tag, keeping the target.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `tag` does not fit in `BITS` bits.
+    ///
+    /// # Parameters
+    /// * `tag` - New tag in the range `0..=TAG_MASK`.
+    #[inline]
+    pub fn set_tag(&mut self, tag: usize) {
+        assert!(
+            tag <= Self::TAG_MASK,
+            "tag {} does not fit in {} bits",
+            tag,
+            BITS
+        );
+        let raw = self.raw();
+        let tagged = (raw & !(Self::TAG_MASK as isize)) | tag as isize;
+        self.store(Self::narrow(tagged));
+    }
+
+    /// Returns the offset to the target with the tag masked off.
+    ///
+    /// # Returns
+    /// * `I` - Offset measured from this pointer to the target.
+    #[inline]
+    pub fn offset(&self) -> I {
+        Self::narrow(self.raw() & !(Self::TAG_MASK as isize))
+    }
+
+    /// Sets the pointer to target the given value, keeping the current tag.
+    ///
+    /// Fails when the distance does not fit in `I` or when its low `BITS` bits are not
+    /// zero, which happens if the pointer or target is not aligned to `1 << BITS`. On
+    /// error the pointer is left untouched.
+    ///
+    /// # Parameters
+    /// * `value` - Target to be referenced by the pointer.
+    ///
+    /// # Returns
+    /// * `Result<(), IntegerOffsetError>` - `Ok` when the offset fits and leaves the tag bits free.
+    pub fn set(&mut self, value: &mut T) -> Result<(), IntegerOffsetError> {
+        let tag = self.tag();
+        let previous = self.0;
+        self.0.set(value)?;
+        let del = self.raw();
+        if del as usize & Self::TAG_MASK != 0 {
+            self.0 = previous;
+            return Err(IntegerOffsetError(IntegerOffsetErrorImpl::Tagged(
+                del, BITS,
+            )));
+        }
+        self.store(Self::narrow(del | tag as isize));
+        Ok(())
+    }
+
+    #[inline]
+    fn raw(&self) -> isize {
+        match self.0.offset().try_into_isize() {
+            Ok(del) => del,
+            Err(_) => unreachable!("tagged offsets are always measured by `set`"),
+        }
+    }
+
+    #[inline]
+    fn narrow(del: isize) -> I {
+        match I::try_from_isize(del) {
+            Ok(offset) => offset,
+            Err(_) => unreachable!("masking tag bits keeps the offset in range"),
+        }
+    }
+
+    #[inline]
+    fn store(&mut self, offset: I) {
+        if let Some(components) = self.0.components_if_ready() {
+            self.0.replace_parts(offset, components);
+        } else {
+            self.0 = self.0.with_offset(offset);
+        }
+    }
+
+    #[inline]
+    unsafe fn resolve_from_base(&self, base: *const u8) -> NonNull<T> {
+        debug_assert!(self.is_ready());
+        let components = self.0.components_if_ready().unchecked_unwrap(
+            "Tried to use an unset relative pointer, this is UB in release mode!",
+        );
+        let self_ptr = self as *const Self as *const u8;
+        let d_self = self_ptr.offset_from(base);
+        let at_self = base.wrapping_offset(d_self);
+        let target = self.offset().add(at_self);
+        T::recompose(NonNull::new(target), components)
+            .unchecked_unwrap("Tried to use an unset relative pointer, this is UB in release mode!")
+    }
+
+    /// Reconstructs a shared reference using a container base pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`SelfRef::get_ref_from_base_unchecked`].
+    ///
+    /// # Parameters
+    /// * `base` - Address of the owning container currently holding the pointer.
+    ///
+    /// # Returns
+    /// * `&'a T` - Shared reference resolved relative to `base`.
+    #[inline]
+    pub unsafe fn get_ref_from_base_unchecked<'a>(&self, base: *const u8) -> &'a T {
+        &*self.resolve_from_base(base).as_ptr()
+    }
+
+    /// Reconstructs a mutable reference using a container base pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`SelfRef::get_mut_from_base_unchecked`].
+    ///
+    /// # Parameters
+    /// * `base` - Address of the owning container currently holding the pointer.
+    ///
+    /// # Returns
+    /// * `&'a mut T` - Exclusive reference resolved relative to `base`.
+    #[inline]
+    pub unsafe fn get_mut_from_base_unchecked<'a>(&self, base: *mut u8) -> &'a mut T {
+        &mut *self.resolve_from_base(base.cast_const()).as_ptr()
+    }
+}
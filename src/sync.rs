@@ -0,0 +1,42 @@
+//! Atomic primitives
+//!
+//! Selects the atomic types backing the concurrent pointers and locks: `loom`
+//! models when running loom tests, `core::sync::atomic` where the target
+//! supports it, and `portable-atomic` for the remaining widths when that
+//! feature is enabled.
+
+#[cfg(all(test, loom))]
+pub(crate) use loom::sync::atomic::{AtomicIsize, AtomicU64, AtomicUsize};
+
+// Native atomics are used wherever the target has them, so enabling
+// `portable-atomic` only fills in the widths the target lacks.
+macro_rules! select_atomics {
+    ($($width:literal => $($atomic:ident),+;)*) => {$($(
+        #[cfg(all(not(all(test, loom)), target_has_atomic = $width))]
+        pub(crate) use core::sync::atomic::$atomic;
+        #[cfg(all(
+            not(all(test, loom)),
+            not(target_has_atomic = $width),
+            feature = "portable-atomic"
+        ))]
+        pub(crate) use portable_atomic::$atomic;
+    )+)*};
+}
+
+select_atomics! {
+    "64" => AtomicU64;
+    "ptr" => AtomicIsize, AtomicUsize;
+}
+
+pub(crate) use core::sync::atomic::Ordering;
+
+/// Backs off while spinning on a contended atomic.
+#[inline]
+pub(crate) fn spin_loop() {
+    #[cfg(all(test, loom))]
+    loom::thread::yield_now();
+    #[cfg(all(not(all(test, loom)), feature = "std"))]
+    std::thread::yield_now();
+    #[cfg(all(not(all(test, loom)), not(feature = "std")))]
+    core::hint::spin_loop();
+}
@@ -169,6 +169,1419 @@ fn from_parts_restores_pointer() {
     assert_eq!(*node.t_ref(), [6, 7, 8, 9]);
 }
 
+#[test]
+fn lifecycle_reset_retarget_take() {
+    let mut node = SelfRefTest::new([1, 2, 3, 4, 5], |x| &mut x[..2]);
+    let first = node.t_ref.parts_if_ready();
+
+    let previous = node.t_ref.retarget(&mut node.t[3..]).unwrap();
+    assert_eq!(previous, first);
+    assert_eq!(*node.t_ref(), [4, 5]);
+
+    let taken = node.t_ref.take();
+    assert!(!node.t_ref.is_ready());
+    assert!(node.t_ref.is_null());
+    assert_eq!(node.t_ref.take(), None);
+
+    let (offset, components) = taken.unwrap();
+    assert_eq!(node.t_ref.replace_parts(offset, components), None);
+    assert_eq!(*node.t_ref(), [4, 5]);
+
+    node.t_ref.reset();
+    assert_eq!(node.t_ref.parts_if_ready(), None);
+}
+
+#[test]
+fn offset_width_conversion() {
+    struct Wide {
+        t_ref: SelfRef<[u8], i32>,
+        t: [u8; 200],
+    }
+
+    let mut wide = Wide {
+        t_ref: SelfRef::null(),
+        t: [7; 200],
+    };
+    wide.t_ref.set(&mut wide.t[150..]).unwrap();
+
+    let narrow: Result<SelfRef<[u8], i8>, _> = wide.t_ref.try_convert();
+    assert!(narrow.is_err());
+
+    let widened: SelfRef<[u8], i64> = wide.t_ref.into();
+    assert_eq!(widened.offset() as isize, wide.t_ref.offset() as isize);
+    assert_eq!(widened.components_if_ready(), Some(50));
+
+    let mut node = SelfRefTest::new([1, 2, 3], |x| &mut x[1..]);
+    let back: SelfRef<[u8], i8> = SelfRef::<[u8], i16>::from(node.t_ref)
+        .try_convert()
+        .unwrap();
+    node.t_ref = back;
+    assert_eq!(*node.t_ref(), [2, 3]);
+
+    let far: SelfRef<[u8], i128> = SelfRef::from_parts(i128::MAX, 1);
+    assert!(far.try_convert::<i64>().is_err());
+    assert!(i128::MIN.try_into_isize().is_err());
+    assert_eq!((-5i128).try_into_isize().unwrap(), -5);
+}
+
+#[test]
+fn casts_check_layout() {
+    #[repr(C, align(4))]
+    struct Words {
+        bytes: [u8; 8],
+        raw: SelfRef<[u8; 4], i8>,
+        slice: SelfRef<[u8], i8>,
+        halves: SelfRef<[u16], i8>,
+    }
+
+    let mut words = Words {
+        bytes: [0; 8],
+        raw: SelfRef::null(),
+        slice: SelfRef::null(),
+        halves: SelfRef::null(),
+    };
+    assert!(!words.raw.cast::<u32>().unwrap().is_ready());
+
+    words.bytes[..4].copy_from_slice(&7u32.to_ne_bytes());
+    words
+        .raw
+        .set((&mut words.bytes[..4]).try_into().unwrap())
+        .unwrap();
+    words.slice.set(&mut words.bytes[..]).unwrap();
+
+    assert!(words.raw.cast::<u16>().is_err());
+    words.slice.cast_slice_into(&mut words.halves).unwrap();
+    assert_eq!(words.halves.components_if_ready(), Some(4));
+    let mut odd = SelfRef::<[[u8; 3]], i8>::null();
+    assert!(words.slice.cast_slice_into(&mut odd).is_err());
+    assert!(odd.is_null());
+
+    let mut words = block_opt(words);
+    let base = &mut words as *mut Words as *mut u8;
+    let word = words.raw.cast::<u32>().unwrap();
+    assert_eq!(unsafe { *word.get_ref_from_base_unchecked(base) }, 7);
+    *unsafe {
+        words
+            .raw
+            .cast_mut::<u32>()
+            .unwrap()
+            .get_mut_from_base_unchecked(base)
+    } = 9;
+    let halves = unsafe { words.halves.get_ref_from_base_unchecked(base) };
+    assert_eq!(halves.len(), 4);
+    assert_eq!(halves[0].to_ne_bytes(), [words.bytes[0], words.bytes[1]]);
+
+    words.slice.set(&mut words.bytes[1..7]).unwrap();
+    assert!(words.slice.cast_slice_into(&mut words.halves).is_err());
+
+    words.bytes[..5].copy_from_slice(b"hello");
+    words.slice.set(&mut words.bytes[..5]).unwrap();
+    let mut words = block_opt(words);
+    let base = &words as *const Words as *const u8;
+    let text = unsafe { words.slice.try_cast_str(base) }.unwrap();
+    assert_eq!(unsafe { text.get_ref_from_base_unchecked(base) }, "hello");
+    assert_eq!(*text.cast_bytes(), words.slice);
+
+    words.bytes[0] = 0xff;
+    let base = &words as *const Words as *const u8;
+    assert!(unsafe { words.slice.try_cast_str(base) }.is_err());
+}
+
+#[test]
+fn tagged_pointer_masks_tag() {
+    #[repr(C, align(4))]
+    struct Slots {
+        current: TaggedSelfRef<u32, i16, 2>,
+        values: [u32; 3],
+        bytes: [u8; 4],
+    }
+
+    let mut slots = Slots {
+        current: TaggedSelfRef::null(),
+        values: [10, 20, 30],
+        bytes: [0; 4],
+    };
+    slots.current.set_tag(1);
+    slots.current.set(&mut slots.values[2]).unwrap();
+    assert_eq!(slots.current.tag(), 1);
+    assert_eq!(slots.current.offset() as usize % 4, 0);
+
+    let ready = slots.current;
+    let mut misaligned: TaggedSelfRef<u8, i16, 2> = TaggedSelfRef::null();
+    assert!(misaligned.set(&mut slots.bytes[1]).is_err());
+    assert!(!misaligned.is_ready());
+
+    slots.current.set_tag(3);
+    assert_ne!(slots.current, ready);
+    let slots = block_opt(slots);
+    let base = &slots as *const Slots as *const u8;
+    assert_eq!(slots.current.tag(), 3);
+    assert_eq!(
+        unsafe { *slots.current.get_ref_from_base_unchecked(base) },
+        30
+    );
+}
+
+#[cfg(all(feature = "std", not(loom)))]
+#[test]
+fn atomic_pointer_single_winner() {
+    use crate::sync::Ordering;
+    use core::sync::atomic::AtomicI32;
+    use std::sync::Arc;
+
+    struct Slots {
+        values: [u32; 4],
+        head: AtomicSelfRef<u32, AtomicI32>,
+    }
+
+    let mut slots = Slots {
+        values: [0, 1, 2, 3],
+        head: AtomicSelfRef::null(),
+    };
+    let offsets: Vec<i32> = (0..4)
+        .map(|i| slots.head.offset_to(&mut slots.values[i]).unwrap())
+        .collect();
+    slots.head.store(offsets[0], Ordering::Release);
+
+    let slots = Arc::new(slots);
+    let winners: usize = (1..4)
+        .map(|i| {
+            let slots = Arc::clone(&slots);
+            let (from, to) = (offsets[0], offsets[i]);
+            std::thread::spawn(move || {
+                slots
+                    .head
+                    .compare_exchange(from, to, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok() as usize
+            })
+        })
+        .map(|handle| handle.join().unwrap())
+        .sum();
+    assert_eq!(winners, 1);
+
+    let base = Arc::as_ptr(&slots) as *const u8;
+    let current = unsafe { slots.head.load_ptr(base, Ordering::Acquire) }.unwrap();
+    assert_ne!(unsafe { *current.as_ptr() }, 0);
+}
+
+#[cfg(all(feature = "std", not(loom)))]
+#[test]
+fn atomic_tagged_pointer_detects_aba() {
+    use crate::sync::Ordering;
+
+    struct Slots {
+        values: [u32; 2],
+        head: AtomicTaggedSelfRef<u32>,
+    }
+
+    let mut slots = Slots {
+        values: [10, 20],
+        head: AtomicTaggedSelfRef::null(),
+    };
+    let a = slots.head.offset_to(&mut slots.values[0]).unwrap();
+    let b = slots.head.offset_to(&mut slots.values[1]).unwrap();
+    slots.head.store(a, Ordering::Release);
+
+    let seen = slots.head.load(Ordering::Acquire);
+    slots.head.store(b, Ordering::Release);
+    slots.head.store(a, Ordering::Release);
+    assert_eq!(slots.head.load(Ordering::Acquire).0, seen.0);
+    assert!(slots
+        .head
+        .compare_exchange(seen, b, Ordering::AcqRel, Ordering::Acquire)
+        .is_err());
+
+    let slots = block_opt(slots);
+    let current = slots.head.load(Ordering::Acquire);
+    assert!(slots
+        .head
+        .compare_exchange(current, b, Ordering::AcqRel, Ordering::Acquire)
+        .is_ok());
+    let base = &slots as *const Slots as *const u8;
+    let offset = slots.head.load(Ordering::Acquire).0;
+    let target = unsafe { slots.head.resolve(offset, base) }.unwrap();
+    assert_eq!(unsafe { *target.as_ptr() }, 20);
+}
+
+#[test]
+fn auto_traits_follow_target() {
+    fn is_send<T: Send>() {}
+    fn is_sync<T: Sync>() {}
+
+    is_send::<SelfRef<u32, i8>>();
+    is_sync::<SelfRef<[u8], i16>>();
+    is_send::<SelfRefCell<[u8; 4], i8>>();
+    is_sync::<SelfRefCell<[u8; 4], i8>>();
+    is_send::<TaggedSelfRef<u64, i32, 3>>();
+}
+
+#[cfg(all(feature = "std", not(loom)))]
+#[test]
+fn cells_cross_threads() {
+    use std::sync::Arc;
+
+    let cell: SelfRefCell<String, i8> = SelfRefCell::new(String::from("moved")).unwrap();
+    let cell = std::thread::spawn(move || {
+        assert_eq!(cell.get(), "moved");
+        cell
+    })
+    .join()
+    .unwrap();
+    assert_eq!(cell.get(), "moved");
+
+    let shared = Arc::new(cell);
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            std::thread::spawn(move || shared.get().len())
+        })
+        .collect();
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), 5);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ref_cell_tracks_borrows() {
+    use std::rc::Rc;
+
+    let cell: Rc<SelfRefRefCell<Vec<u32>, i8>> = Rc::new(SelfRefRefCell::new(vec![1, 2]).unwrap());
+    {
+        let first = cell.borrow();
+        let second = cell.borrow();
+        assert_eq!(first.len() + second.len(), 4);
+        assert!(cell.try_borrow_mut().is_none());
+    }
+    cell.borrow_mut().push(3);
+    {
+        let _writer = cell.borrow_mut();
+        assert!(cell.try_borrow().is_none());
+    }
+
+    let cell = Rc::try_unwrap(cell).unwrap();
+    let mut cell = Box::new(cell);
+    cell.get_mut().push(4);
+    assert_eq!(*cell.borrow(), [1, 2, 3, 4]);
+    assert_eq!(cell.into_inner(), [1, 2, 3, 4]);
+}
+
+#[cfg(all(feature = "std", not(loom)))]
+#[test]
+fn lock_serialises_writers() {
+    use std::sync::Arc;
+
+    let lock: Arc<SelfRefLock<[u64; 8], i8>> = Arc::new(SelfRefLock::new([0; 8]).unwrap());
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let lock = Arc::clone(&lock);
+            std::thread::spawn(move || {
+                for _ in 0..1000 {
+                    let mut guard = lock.write();
+                    guard.iter_mut().for_each(|slot| *slot += 1);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    let reader = lock.read();
+    assert!(reader.iter().all(|slot| *slot == 4000));
+    assert!(lock.try_write().is_none());
+    assert!(lock.try_read().is_some());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cell_standard_traits() {
+    use std::collections::{BTreeSet, HashMap};
+
+    let mut original: SelfRefCell<String, i8> = SelfRefCell::try_from(String::from("a")).unwrap();
+    let copy = original.clone();
+    original.push('b');
+    assert_eq!(&*original, "ab");
+    assert_eq!(&*copy, "a");
+    assert_eq!(copy.get(), "a");
+
+    assert_eq!(format!("{:?} {}", original, copy), "\"ab\" a");
+    assert!(copy < original);
+    assert_eq!(SelfRefCell::<String, i8>::default().len(), 0);
+
+    let mut counts: HashMap<SelfRefCell<String, i8>, u32> = HashMap::new();
+    *counts.entry(copy.clone()).or_default() += 1;
+    *counts.entry(copy).or_default() += 1;
+    assert_eq!(counts.values().copied().collect::<Vec<_>>(), [2]);
+
+    let ordered: BTreeSet<SelfRefCell<u32, i8>> = [3, 1, 2]
+        .into_iter()
+        .map(|n| SelfRefCell::try_from(n).unwrap())
+        .collect();
+    assert_eq!(ordered.iter().map(|n| **n).collect::<Vec<_>>(), [1, 2, 3]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cell_in_place_builders() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let cell: SelfRefCell<[u64; 32], i16> =
+        SelfRefCell::new_in_place(|slot| slot.write([3; 32])).unwrap();
+    assert_eq!(block_opt(cell).get()[31], 3);
+
+    let parsed: Result<SelfRefCell<u32, i8>, _> =
+        SelfRefCell::try_new_with(|slot| "12".parse().map(|n| slot.write(n)));
+    assert_eq!(*parsed.unwrap().get(), 12);
+    let failed: Result<SelfRefCell<u32, i8>, _> =
+        SelfRefCell::try_new_with(|slot| "x".parse().map(|n| slot.write(n)));
+    assert!(matches!(failed, Err(InitError::Init(_))));
+
+    let boxed: Box<SelfRefCell<String, i8>> =
+        SelfRefCell::new_boxed_in_place(|slot| slot.write(String::from("heap"))).unwrap();
+    assert_eq!(boxed.get(), "heap");
+
+    let tracker = Rc::new(());
+    let mut cells: Vec<SelfRefCell<Option<Rc<()>>, i8>> = Vec::new();
+    for _ in 0..3 {
+        SelfRefCell::push_in_place(&mut cells, |slot| slot.write(Some(Rc::clone(&tracker))))
+            .unwrap();
+    }
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        let _ = SelfRefCell::push_in_place(&mut cells, |_| panic!("init failed"));
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(cells.len(), 3);
+    assert_eq!(Rc::strong_count(&tracker), 4);
+
+    let cells = block_opt(cells);
+    assert!(cells
+        .iter()
+        .all(|cell| Rc::ptr_eq(cell.get().as_ref().unwrap(), &tracker)));
+    drop(cells);
+    assert_eq!(Rc::strong_count(&tracker), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cell_slots_fill_in_place() {
+    type Pair = ([u8; 64], String);
+
+    let boxed = SelfRefCell::<Pair, i8>::new_boxed_in_place(|mut slot| {
+        let pair = slot.as_mut_ptr();
+        unsafe {
+            core::ptr::addr_of_mut!((*pair).0).write([1; 64]);
+            core::ptr::addr_of_mut!((*pair).1).write(String::from("right"));
+            slot.assume_init()
+        }
+    })
+    .unwrap();
+    assert_eq!(boxed.get().0[63], 1);
+    assert_eq!(boxed.get().1, "right");
+
+    let doubled: SelfRefCell<u32, i8> = SelfRefCell::new_in_place(|slot| {
+        let mut filled = slot.write(21);
+        *filled *= 2;
+        filled
+    })
+    .unwrap();
+    assert_eq!(*block_opt(doubled).get(), 42);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn views_follow_owner() {
+    const HEADER: usize = 0;
+    const BODY: usize = 1;
+    const CHECKSUM: usize = 2;
+
+    struct Frame {
+        len: usize,
+        bytes: [u8; 16],
+    }
+
+    fn regions(frame: &Frame) -> [&[u8]; 3] {
+        let (header, rest) = frame.bytes.split_at(2);
+        let (body, rest) = rest.split_at(frame.len);
+        [header, body, &rest[..1]]
+    }
+
+    let frame = Frame {
+        len: 4,
+        bytes: *b"HDbodyC.........",
+    };
+    let mut views: SelfRefViews<Frame, [u8], 3, i16> = SelfRefViews::new(frame, regions).unwrap();
+    views.view_mut(CHECKSUM)[0] = b'X';
+
+    let mut views = block_opt(Box::new(block_opt(views)));
+    assert_eq!(views.view(HEADER), b"HD");
+    assert_eq!(views.view(BODY), b"body");
+    assert_eq!(views.view(CHECKSUM), b"X");
+    assert!(views.try_view(3).is_none());
+
+    {
+        let mut frame = views.get_mut();
+        frame.len = 6;
+        frame.bytes[8] = b'Z';
+    }
+    assert_eq!(views.views(), [&b"HD"[..], b"bodyX.", b"Z"]);
+    assert_eq!(views.into_inner().len, 6);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn views_guard_failures_unset_views() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const LEN: usize = 40_000;
+
+    struct Buffer {
+        start: usize,
+        bytes: [u8; LEN],
+    }
+
+    fn regions(buffer: &Buffer) -> [&[u8]; 2] {
+        [&buffer.bytes[..1], &buffer.bytes[buffer.start..]]
+    }
+
+    let buffer = Buffer {
+        start: LEN / 2,
+        bytes: [1; LEN],
+    };
+    let mut views: SelfRefViews<Buffer, [u8], 2, i16> = SelfRefViews::new(buffer, regions).unwrap();
+    assert_eq!(views.view(1).len(), LEN / 2);
+
+    // One end of the buffer is out of `i16` reach, whichever side the views are laid out on.
+    let failed = [0, LEN - 1].into_iter().any(|start| {
+        let mut guard = views.get_mut();
+        guard.start = start;
+        guard.finish().is_err()
+    });
+    assert!(failed);
+    assert!(views.try_view(0).is_none() && views.try_view(1).is_none());
+
+    let mut guard = views.get_mut();
+    guard.start = LEN / 2 + 10;
+    guard.finish().unwrap();
+    assert_eq!(views.view(1).len(), LEN / 2 - 10);
+
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        let mut guard = views.get_mut();
+        guard.start = LEN / 2;
+        panic!("update failed");
+    }));
+    assert!(panicked.is_err());
+    assert!(views.try_view(1).is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cell_exposes_projection() {
+    use std::panic::catch_unwind;
+
+    let mut cell: SelfRefCell<[u16; 4], i8, [u16]> =
+        SelfRefCell::new_with([1, 2, 3, 4], |values| &mut values[2..]).unwrap();
+    cell.get_mut()[0] = 30;
+    let cell = block_opt(cell);
+    assert_eq!(cell.get(), [30, 4]);
+    assert_eq!(cell.len(), 2);
+
+    type ByteCell = SelfRefCell<[u8], i8, [u8]>;
+    let cells: Vec<Box<ByteCell>> = vec![
+        Box::new(SelfRefCell::new_with([1u8, 2], |bytes| &mut bytes[..]).unwrap()),
+        Box::new(SelfRefCell::new_with([3u8, 4, 5, 6], |bytes| &mut bytes[1..3]).unwrap()),
+    ];
+    let cells = block_opt(cells);
+    assert_eq!(cells[0].get(), [1, 2]);
+    assert_eq!(cells[1].get(), [4, 5]);
+
+    assert!(catch_unwind(|| {
+        SelfRefCell::<Vec<u8>, i8, [u8]>::new_with(vec![1], |bytes| &mut bytes[..])
+    })
+    .is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cell_scoped_access() {
+    let mut cell: SelfRefCell<Option<String>, i8> = SelfRefCell::new(None).unwrap();
+    cell.with_mut(|value| *value = Some(String::from("first")));
+    assert_eq!(cell.with(|value| value.as_deref().map(str::len)), Some(5));
+
+    let mut other: SelfRefCell<Option<String>, i8> =
+        SelfRefCell::new(Some(String::from("second"))).unwrap();
+    cell.swap(&mut other);
+    let (mut cell, other) = block_opt((cell, other));
+    assert_eq!(cell.get().as_deref(), Some("second"));
+    assert_eq!(other.get().as_deref(), Some("first"));
+
+    assert_eq!(cell.replace(None).as_deref(), Some("second"));
+    assert_eq!(*cell.get(), None);
+    let mut other = block_opt(other);
+    assert_eq!(other.take().as_deref(), Some("first"));
+    assert!(other.get().is_none());
+
+    let lengths: SelfRefCell<Vec<usize>, i8> = block_opt(cell)
+        .map_into(|value| value.into_iter().map(|s| s.len()).collect())
+        .unwrap();
+    assert!(lengths.get().is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rebuild_cell_follows_owner() {
+    struct Words;
+
+    unsafe impl DependentFamily for Words {
+        type Dependent<'a> = Vec<&'a [u8]>;
+    }
+
+    let cell: RebuildCell<[u8; 16], Words> = RebuildCell::new(*b"one two three  x", |text| {
+        text.split(|byte| *byte == b' ').collect()
+    });
+    assert!(!cell.is_current());
+    assert!(cell.with(|words| words[2] == b"three"));
+    assert!(cell.is_current());
+
+    let mut cell = block_opt(Box::new(block_opt(cell)));
+    assert!(!cell.is_current());
+    let start = cell.owner().as_ptr();
+    assert_eq!(cell.with(|words| words[0].as_ptr()), start);
+    assert_eq!(cell.with(|words| words[1].as_ptr()), start.wrapping_add(4));
+
+    cell.owner_mut()[3] = b'_';
+    assert!(!cell.is_current());
+    assert_eq!(
+        cell.with_mut(|words| words.pop().map(<[u8]>::to_vec)),
+        Some(b"x".to_vec())
+    );
+    assert!(cell.with(|words| words[0] == b"one_two" && words.len() == 3));
+    assert_eq!(&cell.into_owner()[..7], b"one_two");
+
+    let separator = b'e';
+    let cell = RebuildCell::<_, Words, _>::with_builder(*b"one two three  x", move |text| {
+        text.split(|byte| *byte == separator).collect()
+    });
+    let cell = block_opt(Box::new(block_opt(cell)));
+    assert!(cell.with(|words| words.len() == 4 && words[1] == b" two thr"));
+    assert_eq!(cell.with(|words| words[0].as_ptr()), cell.owner().as_ptr());
+}
+
+#[test]
+fn dyn_pointer_layout() {
+    use crate::metadata::dyn_vtable::FatPtr;
+    use core::fmt::Debug;
+
+    let values: [&dyn Debug; 2] = [&1u8, &"two"];
+    for value in values {
+        let raw = value as *const dyn Debug;
+        let parts: FatPtr = unsafe { core::mem::transmute(raw) };
+        assert_eq!(parts.data, raw as *const ());
+        assert_eq!(__dyn_vtable(raw), DynVTable(parts.vtable));
+
+        let rebuilt: *mut dyn Debug =
+            unsafe { __dyn_from_parts(parts.data as *mut u8, __dyn_vtable(raw)) };
+        assert!(core::ptr::eq(rebuilt, raw));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stable_trait_objects() {
+    use core::fmt::Display;
+
+    trait Named {
+        fn name(&self) -> String;
+    }
+
+    impl Named for (u8, char) {
+        fn name(&self) -> String {
+            format!("{}{}", self.1, self.0)
+        }
+    }
+
+    trait Scaled<T> {
+        fn scaled(&self) -> T;
+    }
+
+    impl Scaled<u16> for (u8, char) {
+        fn scaled(&self) -> u16 {
+            u16::from(self.0) * 100
+        }
+    }
+
+    dyn_recomposition!(dyn Named, dyn Scaled<u16> + Send,);
+
+    struct Holder {
+        pair: (u8, char),
+        named: SelfRef<dyn Named, i8>,
+        shown: SelfRef<dyn Display + Send + Sync, i8>,
+        scaled: SelfRef<dyn Scaled<u16> + Send, i8>,
+    }
+
+    let mut holder = Holder {
+        pair: (7, 'x'),
+        named: SelfRef::null(),
+        shown: SelfRef::null(),
+        scaled: SelfRef::null(),
+    };
+    holder.named.set(&mut holder.pair).unwrap();
+    holder.shown.set(&mut holder.pair.1).unwrap();
+    holder.scaled.set(&mut holder.pair).unwrap();
+
+    let holder = block_opt(Box::new(block_opt(holder)));
+    let base = &*holder as *const Holder as *const u8;
+    let named = unsafe { holder.named.get_ref_from_base_unchecked(base) };
+    let shown = unsafe { holder.shown.get_ref_from_base_unchecked(base) };
+    let scaled = unsafe { holder.scaled.get_ref_from_base_unchecked(base) };
+    assert_eq!(named.name(), "x7");
+    assert_eq!(shown.to_string(), "x");
+    assert_eq!(scaled.scaled(), 700);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn any_pointers_downcast() {
+    use core::any::Any;
+
+    struct Holder {
+        value: u32,
+        any: SelfRef<dyn Any, i8>,
+    }
+
+    let mut holder = Holder {
+        value: 5,
+        any: SelfRef::null(),
+    };
+    holder.any.set(&mut holder.value).unwrap();
+
+    let mut holder = block_opt(holder);
+    let base = &mut holder as *mut Holder as *mut u8;
+    unsafe {
+        assert!(holder.any.downcast_ref_from_base::<u8>(base).is_none());
+        *holder.any.downcast_mut_from_base::<u32>(base).unwrap() += 1;
+        assert_eq!(holder.any.downcast_ref_from_base::<u32>(base), Some(&6));
+    }
+
+    type AnyCell = SelfRefCell<dyn Any, i8, dyn Any>;
+    let cells: Vec<Box<AnyCell>> = vec![
+        Box::new(SelfRefCell::new_with(7u32, |value| value as &mut dyn Any).unwrap()),
+        Box::new(SelfRefCell::new_with("text", |value| value as &mut dyn Any).unwrap()),
+    ];
+    let cells = block_opt(cells);
+    assert_eq!(cells[0].downcast_ref::<u32>(), Some(&7));
+    assert_eq!(cells[1].downcast_ref::<&str>(), Some(&"text"));
+    assert!(cells[1].downcast_ref::<u32>().is_none());
+}
+
+#[test]
+fn compact_lengths() {
+    use core::mem::size_of;
+
+    #[cfg(not(feature = "debug-guards"))]
+    {
+        assert_eq!(size_of::<SelfRef<Len8<[u8]>, i8>>(), 3);
+        assert_eq!(size_of::<SelfRef<Len16<str>, i16>>(), 6);
+        assert_eq!(size_of::<SelfRef<u32, i8>>(), 2);
+    }
+    assert_eq!(
+        size_of::<<Len32<[u64]> as PointerRecomposition>::Components>(),
+        4
+    );
+
+    struct Record {
+        bytes: [u8; 300],
+        short: SelfRef<Len8<[u8]>, i16>,
+        text: SelfRef<Len16<str>, i16>,
+    }
+
+    let mut record = Record {
+        bytes: [b'a'; 300],
+        short: SelfRef::null(),
+        text: SelfRef::null(),
+    };
+    assert!(Len8::from_mut(&mut record.bytes[..256]).is_err());
+    record
+        .short
+        .set(Len8::from_mut(&mut record.bytes[45..300]).unwrap())
+        .unwrap();
+    let text = core::str::from_utf8_mut(&mut record.bytes[..3]).unwrap();
+    record.text.set(Len16::from_mut(text).unwrap()).unwrap();
+    assert_eq!(record.short.components_if_ready(), Some(255));
+
+    let record = block_opt(record);
+    let base = &record as *const Record as *const u8;
+    let short = unsafe { record.short.get_ref_from_base_unchecked(base) };
+    let text = unsafe { record.text.get_ref_from_base_unchecked(base) };
+    assert_eq!(short.len(), 255);
+    assert!(short.iter().all(|byte| *byte == b'a'));
+    assert_eq!(&**text, "aaa");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_byte_strings_survive_moves() {
+    use std::ffi::{CStr, OsStr};
+    use std::path::Path;
+
+    const NAME: [u8; 16] = *b"dir/file.txt\0...";
+
+    fn text(bytes: &[u8; 16]) -> &str {
+        core::str::from_utf8(&bytes[..12]).unwrap()
+    }
+
+    let c_name: SelfRefViews<[u8; 16], CStr, 1, i8> =
+        SelfRefViews::new(NAME, |bytes| [CStr::from_bytes_until_nul(bytes).unwrap()]).unwrap();
+    let os_name: SelfRefViews<[u8; 16], OsStr, 1, i8> =
+        SelfRefViews::new(NAME, |bytes| [OsStr::new(&text(bytes)[4..])]).unwrap();
+    let path: SelfRefViews<[u8; 16], Path, 1, i8> =
+        SelfRefViews::new(NAME, |bytes| [Path::new(text(bytes))]).unwrap();
+
+    let (c_name, os_name, path) = *block_opt(Box::new(block_opt((c_name, os_name, path))));
+    assert_eq!(c_name.view(0).to_bytes(), b"dir/file.txt");
+    assert_eq!(os_name.view(0), "file.txt");
+    assert_eq!(path.view(0).file_name(), Some(os_name.view(0)));
+    assert_eq!(path.view(0).parent(), Some(Path::new("dir")));
+}
+
+#[test]
+fn slice_tailed_dsts() {
+    use core::mem::{align_of_val, size_of_val};
+
+    struct Packet<T: ?Sized> {
+        len: u16,
+        payload: T,
+    }
+
+    // Three bytes of padding before the tail.
+    struct Words<T: ?Sized> {
+        tag: u8,
+        values: T,
+    }
+
+    // Trailing padding after a short tail.
+    struct Padded<T: ?Sized> {
+        wide: u64,
+        flag: bool,
+        bytes: T,
+    }
+
+    struct Generic<X, T: ?Sized> {
+        head: X,
+        tail: T,
+    }
+
+    slice_tail_recomposition!(Packet<[u8]>, Words<[u32]>, Padded<[u8]>);
+    slice_tail_recomposition!(impl<X> Generic<X, [u16]>);
+
+    struct Owner {
+        packet: Packet<[u8; 5]>,
+        words: Words<[u32; 2]>,
+        padded: Padded<[u8; 1]>,
+        empty: Generic<u8, [u16; 0]>,
+        packet_ref: SelfRef<Packet<[u8]>, i16>,
+        words_ref: SelfRef<Words<[u32]>, i16>,
+        padded_ref: SelfRef<Padded<[u8]>, i16>,
+        empty_ref: SelfRef<Generic<u8, [u16]>, i16>,
+    }
+
+    let mut owner = Owner {
+        packet: Packet {
+            len: 5,
+            payload: *b"hello",
+        },
+        words: Words {
+            tag: 9,
+            values: [1, u32::MAX],
+        },
+        padded: Padded {
+            wide: 3,
+            flag: true,
+            bytes: [4],
+        },
+        empty: Generic { head: 6, tail: [] },
+        packet_ref: SelfRef::null(),
+        words_ref: SelfRef::null(),
+        padded_ref: SelfRef::null(),
+        empty_ref: SelfRef::null(),
+    };
+    owner.packet_ref.set(&mut owner.packet).unwrap();
+    owner.words_ref.set(&mut owner.words).unwrap();
+    owner.padded_ref.set(&mut owner.padded).unwrap();
+    owner.empty_ref.set(&mut owner.empty).unwrap();
+    assert_eq!(owner.words_ref.components_if_ready(), Some(2));
+
+    let owner = block_opt(owner);
+    let base = &owner as *const Owner as *const u8;
+    let packet = unsafe { owner.packet_ref.get_ref_from_base_unchecked(base) };
+    let words = unsafe { owner.words_ref.get_ref_from_base_unchecked(base) };
+    let padded = unsafe { owner.padded_ref.get_ref_from_base_unchecked(base) };
+    let empty = unsafe { owner.empty_ref.get_ref_from_base_unchecked(base) };
+
+    assert_eq!((packet.len, &packet.payload), (5, &b"hello"[..]));
+    assert_eq!((words.tag, &words.values), (9, &[1, u32::MAX][..]));
+    assert_eq!(size_of_val(words), size_of_val(&owner.words));
+    assert_eq!(
+        (padded.wide, padded.flag, &padded.bytes),
+        (3, true, &[4][..])
+    );
+    assert_eq!(size_of_val(padded), 16);
+    assert_eq!(align_of_val(padded), 8);
+    assert_eq!((empty.head, empty.tail.len()), (6, 0));
+}
+
+#[test]
+fn registry_pointers_survive_byte_copies() {
+    use core::any::Any;
+    use core::mem::MaybeUninit;
+
+    trait Shape: Any {
+        fn area(&self) -> u32;
+    }
+
+    struct Square(u32);
+    struct Rect(u16, u16);
+    struct Circle;
+
+    impl Shape for Square {
+        fn area(&self) -> u32 {
+            self.0 * self.0
+        }
+    }
+
+    impl Shape for Rect {
+        fn area(&self) -> u32 {
+            u32::from(self.0) * u32::from(self.1)
+        }
+    }
+
+    impl Shape for Circle {
+        fn area(&self) -> u32 {
+            3
+        }
+    }
+
+    vtable_registry! {
+        struct Shapes(dyn Shape): u16 { Square, Rect }
+    }
+
+    #[repr(C)]
+    struct Scene {
+        square: Square,
+        rect: Rect,
+        first: SelfRef<Shapes, i16>,
+        second: SelfRef<Shapes, i16>,
+    }
+
+    assert!(Shapes::from_ref(&Circle).is_none());
+
+    let mut scene = Scene {
+        square: Square(4),
+        rect: Rect(2, 3),
+        first: SelfRef::null(),
+        second: SelfRef::null(),
+    };
+    scene
+        .first
+        .set(Shapes::from_mut(&mut scene.square).unwrap())
+        .unwrap();
+    scene
+        .second
+        .set(Shapes::from_mut(&mut scene.rect).unwrap())
+        .unwrap();
+    assert_eq!(scene.first.components_if_ready(), Some(0));
+    assert_eq!(scene.second.components_if_ready(), Some(1));
+
+    // Stands in for writing the bytes out and loading them elsewhere.
+    let mut loaded = MaybeUninit::<Scene>::uninit();
+    unsafe {
+        core::ptr::copy_nonoverlapping(&scene as *const Scene, loaded.as_mut_ptr(), 1);
+    }
+    let loaded = unsafe { loaded.assume_init() };
+
+    let base = &loaded as *const Scene as *const u8;
+    let first = unsafe { loaded.first.get_ref_from_base_unchecked(base) };
+    let second = unsafe { loaded.second.get_ref_from_base_unchecked(base) };
+    assert_eq!((first.index(), first.area()), (0, 16));
+    assert_eq!((second.index(), second.area()), (1, 6));
+    assert!(core::ptr::eq(
+        &**second as *const dyn Shape as *const u8,
+        &loaded.rect as *const Rect as *const u8
+    ));
+}
+
+#[test]
+fn full_registry_lookups() {
+    use core::any::Any;
+
+    trait Numbered: Any {
+        fn number(&self) -> usize;
+    }
+
+    struct Member<const N: usize>;
+    struct Stranger;
+
+    impl<const N: usize> Numbered for Member<N> {
+        fn number(&self) -> usize {
+            N
+        }
+    }
+
+    impl Numbered for Stranger {
+        fn number(&self) -> usize {
+            usize::MAX
+        }
+    }
+
+    vtable_registry! {
+        struct Full(dyn Numbered): u8 {
+            Member<0>, Member<1>, Member<2>, Member<3>, Member<4>, Member<5>, Member<6>, Member<7>, Member<8>, Member<9>,
+            Member<10>, Member<11>, Member<12>, Member<13>, Member<14>, Member<15>, Member<16>, Member<17>, Member<18>, Member<19>,
+            Member<20>, Member<21>, Member<22>, Member<23>, Member<24>, Member<25>, Member<26>, Member<27>, Member<28>, Member<29>,
+            Member<30>, Member<31>, Member<32>, Member<33>, Member<34>, Member<35>, Member<36>, Member<37>, Member<38>, Member<39>,
+            Member<40>, Member<41>, Member<42>, Member<43>, Member<44>, Member<45>, Member<46>, Member<47>, Member<48>, Member<49>,
+            Member<50>, Member<51>, Member<52>, Member<53>, Member<54>, Member<55>, Member<56>, Member<57>, Member<58>, Member<59>,
+            Member<60>, Member<61>, Member<62>, Member<63>, Member<64>, Member<65>, Member<66>, Member<67>, Member<68>, Member<69>,
+            Member<70>, Member<71>, Member<72>, Member<73>, Member<74>, Member<75>, Member<76>, Member<77>, Member<78>, Member<79>,
+            Member<80>, Member<81>, Member<82>, Member<83>, Member<84>, Member<85>, Member<86>, Member<87>, Member<88>, Member<89>,
+            Member<90>, Member<91>, Member<92>, Member<93>, Member<94>, Member<95>, Member<96>, Member<97>, Member<98>, Member<99>,
+            Member<100>, Member<101>, Member<102>, Member<103>, Member<104>, Member<105>, Member<106>, Member<107>, Member<108>, Member<109>,
+            Member<110>, Member<111>, Member<112>, Member<113>, Member<114>, Member<115>, Member<116>, Member<117>, Member<118>, Member<119>,
+            Member<120>, Member<121>, Member<122>, Member<123>, Member<124>, Member<125>, Member<126>, Member<127>, Member<128>, Member<129>,
+            Member<130>, Member<131>, Member<132>, Member<133>, Member<134>, Member<135>, Member<136>, Member<137>, Member<138>, Member<139>,
+            Member<140>, Member<141>, Member<142>, Member<143>, Member<144>, Member<145>, Member<146>, Member<147>, Member<148>, Member<149>,
+            Member<150>, Member<151>, Member<152>, Member<153>, Member<154>, Member<155>, Member<156>, Member<157>, Member<158>, Member<159>,
+            Member<160>, Member<161>, Member<162>, Member<163>, Member<164>, Member<165>, Member<166>, Member<167>, Member<168>, Member<169>,
+            Member<170>, Member<171>, Member<172>, Member<173>, Member<174>, Member<175>, Member<176>, Member<177>, Member<178>, Member<179>,
+            Member<180>, Member<181>, Member<182>, Member<183>, Member<184>, Member<185>, Member<186>, Member<187>, Member<188>, Member<189>,
+            Member<190>, Member<191>, Member<192>, Member<193>, Member<194>, Member<195>, Member<196>, Member<197>, Member<198>, Member<199>,
+            Member<200>, Member<201>, Member<202>, Member<203>, Member<204>, Member<205>, Member<206>, Member<207>, Member<208>, Member<209>,
+            Member<210>, Member<211>, Member<212>, Member<213>, Member<214>, Member<215>, Member<216>, Member<217>, Member<218>, Member<219>,
+            Member<220>, Member<221>, Member<222>, Member<223>, Member<224>, Member<225>, Member<226>, Member<227>, Member<228>, Member<229>,
+            Member<230>, Member<231>, Member<232>, Member<233>, Member<234>, Member<235>, Member<236>, Member<237>, Member<238>, Member<239>,
+            Member<240>, Member<241>, Member<242>, Member<243>, Member<244>, Member<245>, Member<246>, Member<247>, Member<248>, Member<249>,
+            Member<250>, Member<251>, Member<252>, Member<253>, Member<254>, Member<255>,
+        }
+    }
+
+    // Looking past the 256th member must not step the index beyond `u8::MAX`.
+    assert!(Full::from_ref(&Stranger).is_none());
+    assert_eq!(Full::from_ref(&Member::<255>).unwrap().index(), 255);
+    assert_eq!(Full::from_ref(&Member::<0>).unwrap().index(), 0);
+
+    let last = Member::<255>;
+    let ptr = core::ptr::NonNull::new(&last as *const Member<255> as *mut u8);
+    let rebuilt = unsafe { <Full as PointerRecomposition>::recompose(ptr, 255) };
+    assert_eq!(unsafe { rebuilt.unwrap().as_ref() }.number(), 255);
+}
+
+#[test]
+fn field_refs_resolve_from_owner() {
+    use core::mem::{offset_of, size_of};
+
+    struct Record {
+        id: u32,
+        name: [u8; 4],
+        flags: (bool, u16),
+    }
+
+    struct Packet<T: ?Sized> {
+        kind: u16,
+        payload: T,
+    }
+
+    slice_tail_recomposition!(Packet<[u32]>);
+
+    const NAME: FieldRef<Record, [u8; 4], { offset_of!(Record, name) }> = field_ref!(Record, name);
+    let flags = field_ref!(Record, flags);
+    assert_eq!(size_of::<FieldRef<Record, [u8; 4], 4>>(), 0);
+    assert_eq!(NAME.offset(), offset_of!(Record, name));
+
+    let mut record = Record {
+        id: 7,
+        name: *b"abcd",
+        flags: (true, 9),
+    };
+    flags.get_mut(&mut record).1 += 1;
+
+    let record = block_opt(record);
+    assert_eq!(record.id, 7);
+    assert_eq!(NAME.get(&record), b"abcd");
+    assert_eq!(flags.get(&record), &(true, 10));
+
+    // The tail starts at the same offset in every instantiation of `Packet`.
+    let payload: FieldRef<Packet<[u32]>, [u32], { offset_of!(Packet<[u32; 0]>, payload) }> =
+        unsafe { FieldRef::new() };
+    let mut packet = Packet {
+        kind: 2,
+        payload: [5, 6, 7],
+    };
+    let packet: &mut Packet<[u32]> = &mut packet;
+    payload.get_tail_mut(packet)[0] = 4;
+    assert_eq!(packet.kind, 2);
+    assert_eq!(payload.get_tail(packet), &[4, 6, 7]);
+}
+
+#[test]
+fn offset_distances() {
+    assert_eq!((i8::MIN_DISTANCE, i8::MAX_DISTANCE), (-128, 127));
+    assert_eq!(
+        (i16::MIN_DISTANCE, i16::MAX_DISTANCE),
+        (i16::MIN as isize, i16::MAX as isize)
+    );
+    assert_eq!(i128::MAX_DISTANCE, isize::MAX);
+    assert_eq!(isize::MIN_DISTANCE, isize::MIN);
+
+    // Leaves room for the larger `SelfRef` of `debug-guards`.
+    struct Fits {
+        bytes: [u8; 100],
+        ptr: SelfRef<u8, i8>,
+    }
+
+    struct Empty;
+
+    assert_offset_fits!(Fits, i8);
+    assert_offset_fits!(Empty, i8);
+    assert!(__offset_fits::<i8>(128));
+    assert!(!__offset_fits::<i8>(129));
+    assert!(__offset_fits::<i16>(129));
+
+    let mut fits = Fits {
+        bytes: [0; 100],
+        ptr: SelfRef::null(),
+    };
+    fits.ptr.set(&mut fits.bytes[0]).unwrap();
+}
+
+#[test]
+fn auto_offset_widths() {
+    use core::mem::{size_of, size_of_val};
+
+    assert_eq!(smallest_offset_width(0), 1);
+    assert_eq!(smallest_offset_width(128), 1);
+    assert_eq!(smallest_offset_width(129), 2);
+    assert_eq!(smallest_offset_width(1 << 16), 4);
+    assert_eq!(smallest_offset_width(1 << 31), 4);
+    assert_eq!(smallest_offset_width((1 << 31) + 1), 8);
+
+    type Small = [u16; 8];
+    type Large = [u8; 40_000];
+    assert_eq!(size_of::<auto_offset!(Small)>(), 1);
+    assert_eq!(size_of::<auto_offset!(Large)>(), 4);
+    assert_eq!(size_of::<OffsetOfWidth<2>>(), 2);
+
+    struct Slots {
+        values: Small,
+        current: SelfRef<u16, auto_offset!(Small)>,
+    }
+    assert_offset_fits!(Slots, auto_offset!(Small));
+
+    let mut slots = Slots {
+        values: [0, 1, 2, 3, 4, 5, 6, 7],
+        current: SelfRef::null(),
+    };
+    slots.current.set(&mut slots.values[7]).unwrap();
+    let mut slots = block_opt(slots);
+    assert_eq!(unsafe { *slots.current.as_ref_unchecked() }, 7);
+
+    self_referential! {
+        #[derive(Clone)]
+        struct Narrow {
+            values: [u16; 8],
+            label: u8,
+        }
+        refs(auto) {
+            last: SelfRef<u16> { get: last } = values[7],
+            tail: SelfRef<[u16]> { get: tail } = values[4..],
+        }
+    }
+
+    self_referential! {
+        struct Wide {
+            bytes: [u8; 200],
+        }
+        refs(auto) {
+            end: SelfRef<u8> { get: end } = bytes[199],
+        }
+    }
+
+    let narrow = block_opt(Narrow::new([0, 1, 2, 3, 4, 5, 6, 7], 9).unwrap().clone());
+    assert_eq!(size_of_val(&narrow.last), size_of::<SelfRef<u16, i8>>());
+    assert_eq!(
+        (*narrow.last(), narrow.tail(), narrow.label),
+        (7, &[4, 5, 6, 7][..], 9)
+    );
+
+    let wide = block_opt(Wide::new([3; 200]).unwrap());
+    assert_eq!(size_of_val(&wide.end), size_of::<SelfRef<u8, i16>>());
+    assert_eq!(*wide.end(), 3);
+}
+
+#[test]
+fn const_construction() {
+    use core::mem::offset_of;
+
+    #[repr(C)]
+    struct Entry {
+        name: [u8; 3],
+        value: u32,
+        name_ref: SelfRef<[u8], i8>,
+        value_ref: SelfRef<u32, i8>,
+    }
+
+    static ENTRY: Entry = Entry {
+        name: *b"abc",
+        value: 42,
+        name_ref: SelfRef::<[u8], i8>::from_field_offsets(
+            offset_of!(Entry, name_ref),
+            offset_of!(Entry, name) + 1,
+            2,
+        ),
+        value_ref: SelfRef::<u32, i8>::from_field_offsets(
+            offset_of!(Entry, value_ref),
+            offset_of!(Entry, value),
+            (),
+        ),
+    };
+    const UNSET: SelfRef<u32, i16> = SelfRef::null();
+    const _: () = assert!(!UNSET.is_ready());
+    const ITSELF: SelfRef<u32, i8> = SelfRef::from_parts(0, ());
+    const _: () = assert!(ITSELF.is_ready());
+    assert!(UNSET.is_null() && ITSELF.is_null());
+
+    let base = &ENTRY as *const Entry as *const u8;
+    assert_eq!(
+        unsafe { ENTRY.name_ref.get_ref_from_base_unchecked(base) },
+        b"bc"
+    );
+    assert_eq!(
+        unsafe { *ENTRY.value_ref.get_ref_from_base_unchecked(base) },
+        42
+    );
+
+    static BYTES: SelfRefCell<[u8; 3], i8> = SelfRefCell::<_, i8>::const_new(*b"xyz");
+    static WIDE: SelfRefCell<u64, i16> = SelfRefCell::<_, i16>::const_new(u64::MAX);
+    assert_eq!(BYTES.get(), b"xyz");
+    assert_eq!(*WIDE.get(), u64::MAX);
+    assert_eq!(
+        WIDE.clone(),
+        SelfRefCell::<u64, i16>::new(u64::MAX).unwrap()
+    );
+}
+
+#[test]
+fn const_tables_are_position_independent() {
+    use core::mem::offset_of;
+
+    struct Transition {
+        on: u8,
+        to: SelfRef<Transition, i16>,
+        label: SelfRef<[u8], i16>,
+        name: SelfRef<str, i16>,
+    }
+
+    unsafe impl PointerRecomposition for Transition {
+        type Components = ();
+
+        fn decompose(_: &Self) {}
+
+        unsafe fn recompose(ptr: Ptr<u8>, (): ()) -> Ptr<Self> {
+            ptr.map(core::ptr::NonNull::cast)
+        }
+    }
+
+    const POOL: ConstPool<9, 3> = ConstPool::new()
+        .push(b"\x01\x02")
+        .push_str("start")
+        .push(&[0xff, 0xfe]);
+    type Table = ConstTable<Transition, 2, { POOL.len() }>;
+
+    const fn transition(index: usize, to: usize, label: usize) -> Transition {
+        let at = Table::record_position(index);
+        let (start, len) = POOL.entry(label);
+        Transition {
+            on: index as u8,
+            to: SelfRef::<Transition, i16>::from_field_offsets(
+                at + offset_of!(Transition, to),
+                Table::record_position(to),
+                (),
+            ),
+            label: SelfRef::<[u8], i16>::from_field_offsets(
+                at + offset_of!(Transition, label),
+                Table::pool_position(start),
+                len,
+            ),
+            name: SelfRef::<str, i16>::from_field_offsets(
+                at + offset_of!(Transition, name),
+                Table::pool_position(start),
+                len,
+            ),
+        }
+    }
+
+    static TABLE: Table = ConstTable::new(
+        [transition(0, 1, 0), transition(1, 0, 1)],
+        POOL.into_bytes(),
+    );
+
+    assert_eq!((POOL.len(), POOL.entry(2)), (9, (7, 2)));
+    let first = &TABLE.records()[0];
+    let second = TABLE.record(&first.to).unwrap();
+    assert_eq!(second.on, 1);
+    assert_eq!(TABLE.bytes(&first.label), Some(&[1, 2][..]));
+    assert_eq!(TABLE.text(&second.name), Some("start"));
+    assert_eq!(TABLE.text(&first.name), Some("\u{1}\u{2}"));
+
+    // Links held outside the table, unset links and misaligned targets are rejected.
+    let outside = first.to;
+    assert!(TABLE.record(&outside).is_none());
+    let unset = ConstTable::<Transition, 1, 0>::new(
+        [Transition {
+            on: 0,
+            to: SelfRef::null(),
+            label: SelfRef::null(),
+            name: SelfRef::null(),
+        }],
+        [],
+    );
+    assert!(unset.record(&unset.records()[0].to).is_none());
+    let skewed = ConstTable::<Transition, 1, 2>::new(
+        [Transition {
+            on: 0,
+            to: SelfRef::<Transition, i16>::from_field_offsets(offset_of!(Transition, to), 1, ()),
+            label: SelfRef::<[u8], i16>::from_field_offsets(
+                offset_of!(Transition, label),
+                ConstTable::<Transition, 1, 2>::pool_position(1),
+                2,
+            ),
+            name: SelfRef::<str, i16>::from_field_offsets(
+                offset_of!(Transition, name),
+                ConstTable::<Transition, 1, 2>::pool_position(0),
+                2,
+            ),
+        }],
+        [0xc3, 0x28],
+    );
+    assert!(skewed.record(&skewed.records()[0].to).is_none());
+    assert!(skewed.bytes(&skewed.records()[0].label).is_none());
+    assert!(skewed.text(&skewed.records()[0].name).is_none());
+
+    // A byte-for-byte copy, as when the image is flashed elsewhere, resolves the same way.
+    let copy = block_opt(unsafe { core::ptr::read(&TABLE) });
+    let moved = copy.record(&copy.records()[1].to).unwrap();
+    assert_eq!(copy.text(&copy.records()[1].name), Some("start"));
+    assert!(core::ptr::eq(moved, &copy.records()[0]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn self_referential_structs() {
+    self_referential! {
+        /// Views into one buffer and the label beside it.
+        #[derive(Clone)]
+        pub(crate) struct Frame<T: Copy + PointerRecomposition, L: Copy + core::fmt::Debug> {
+            pub values: [T; 3],
+            label: L,
+            bytes: [u8; 200],
+        }
+        refs {
+            pub(crate) last: SelfRef<T> { get: last, get_mut: last_mut } = values[2],
+            tail: SelfRef<[u8], i16> {
+                try_get: try_tail,
+                try_get_mut: try_tail_mut,
+                get: tail,
+            } = bytes[198..],
+            whole: SelfRef<[u8; 200], i16> { get: whole } = bytes,
+        }
+    }
+
+    let mut frame = Frame::new([1u32, 2, 3], 'x', [0; 200]).unwrap();
+    *frame.last_mut() = 30;
+    frame.try_tail_mut().unwrap().copy_from_slice(b"ok");
+
+    let copy = frame.clone();
+    let frames = block_opt(vec![frame, copy]);
+    for frame in &frames {
+        assert_eq!(
+            (*frame.last(), frame.values, frame.label),
+            (30, [1, 2, 30], 'x')
+        );
+        assert_eq!(frame.tail(), b"ok");
+        assert_eq!(frame.try_tail(), Some(&b"ok"[..]));
+        assert_eq!(&frame.whole()[198..], b"ok");
+        assert!(core::ptr::eq(frame.whole(), &frame.bytes));
+    }
+
+    self_referential! {
+        struct Far {
+            bytes: [u8; 300],
+        }
+        refs {
+            end: SelfRef<u8, i8> { get: end } = bytes[299],
+        }
+    }
+    assert!(Far::new([0; 300]).is_err());
+
+    // Code beside the struct can still unset a pointer; the accessors notice.
+    let mut frame = frames.into_iter().next().unwrap();
+    frame.tail.reset();
+    assert_eq!(frame.try_tail(), None);
+    assert!(frame.try_tail_mut().is_none());
+    assert_eq!(*frame.last(), 30);
+    let result = std::panic::catch_unwind(move || frame.tail().len());
+    assert!(result.is_err());
+}
+
+#[test]
+fn compact_layout_is_specified() {
+    type Compact = SelfRef<Len16<str>, i16>;
+    let byte = |ptr: &Compact, at: usize| unsafe { *(ptr as *const Compact as *const u8).add(at) };
+
+    let mut ptr: Compact = SelfRef::null();
+    assert_eq!(byte(&ptr, 0), 0);
+    ptr = SelfRef::from_parts(-300, 5);
+    assert_eq!(byte(&ptr, 0), 1);
+
+    #[cfg(not(feature = "debug-guards"))]
+    {
+        use core::mem::size_of;
+
+        let pair = |at: usize| [byte(&ptr, at), byte(&ptr, at + 1)];
+        assert_eq!(size_of::<Compact>(), 6);
+        assert_eq!(pair(2), (-300i16).to_ne_bytes());
+        assert_eq!(pair(4), 5u16.to_ne_bytes());
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+#[test]
+fn portable_atomics_are_additive() {
+    use core::sync::atomic::Ordering;
+
+    struct Slots {
+        values: [u32; 2],
+        native: AtomicSelfRef<u32, core::sync::atomic::AtomicI32>,
+        portable: AtomicSelfRef<u32, portable_atomic::AtomicI32>,
+    }
+
+    let mut slots = Slots {
+        values: [1, 2],
+        native: AtomicSelfRef::null(),
+        portable: AtomicSelfRef::null(),
+    };
+    let target = &mut slots.values[1] as *mut u32;
+    slots.native.set(target, Ordering::Release).unwrap();
+    slots.portable.set(target, Ordering::Release).unwrap();
+
+    let slots = block_opt(slots);
+    let base = &slots as *const Slots as *const u8;
+    let native = unsafe { slots.native.load_ptr(base, Ordering::Acquire) };
+    let portable = unsafe { slots.portable.load_ptr(base, Ordering::Acquire) };
+    assert_eq!(native, portable);
+    assert_eq!(unsafe { *native.unwrap().as_ptr() }, 2);
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn try_accessors() {
@@ -193,6 +1606,110 @@ fn check_copy() {
     }
 }
 
+#[cfg(loom)]
+mod loom_models {
+    use super::*;
+    use crate::sync::Ordering;
+    use loom::sync::atomic::AtomicI32;
+    use loom::sync::Arc;
+
+    struct Slots {
+        values: [u32; 3],
+        head: AtomicSelfRef<u32, AtomicI32>,
+    }
+
+    #[test]
+    fn concurrent_exchange_has_one_winner() {
+        loom::model(|| {
+            let mut slots = Slots {
+                values: [0, 1, 2],
+                head: AtomicSelfRef::null(),
+            };
+            let offsets: [i32; 3] =
+                core::array::from_fn(|i| slots.head.offset_to(&mut slots.values[i]).unwrap());
+            slots.head.store(offsets[0], Ordering::Release);
+            let slots = Arc::new(slots);
+
+            let handles: Vec<_> = (1..3)
+                .map(|i| {
+                    let slots = slots.clone();
+                    loom::thread::spawn(move || {
+                        slots
+                            .head
+                            .compare_exchange(
+                                offsets[0],
+                                offsets[i],
+                                Ordering::AcqRel,
+                                Ordering::Acquire,
+                            )
+                            .is_ok()
+                    })
+                })
+                .collect();
+            let winners = handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .filter(|won| *won)
+                .count();
+            assert_eq!(winners, 1);
+
+            let base = Arc::as_ptr(&slots) as *const u8;
+            let current = unsafe { slots.head.load_ptr(base, Ordering::Acquire) }.unwrap();
+            assert_ne!(unsafe { *current.as_ptr() }, 0);
+        });
+    }
+
+    #[test]
+    fn lock_excludes_concurrent_writers() {
+        loom::model(|| {
+            let lock: Arc<SelfRefLock<[u32; 2], i8>> = Arc::new(SelfRefLock::new([0; 2]).unwrap());
+            let other = {
+                let lock = lock.clone();
+                loom::thread::spawn(move || {
+                    let mut guard = lock.write();
+                    guard[0] += 1;
+                    guard[1] += 1;
+                })
+            };
+            {
+                let mut guard = lock.write();
+                guard[0] += 1;
+                guard[1] += 1;
+            }
+            other.join().unwrap();
+            assert_eq!(*lock.read(), [2, 2]);
+        });
+    }
+
+    #[test]
+    fn swap_returns_every_offset_once() {
+        loom::model(|| {
+            let mut slots = Slots {
+                values: [0, 1, 2],
+                head: AtomicSelfRef::null(),
+            };
+            let offsets: [i32; 3] =
+                core::array::from_fn(|i| slots.head.offset_to(&mut slots.values[i]).unwrap());
+            slots.head.store(offsets[0], Ordering::Release);
+            let slots = Arc::new(slots);
+
+            let other = {
+                let slots = slots.clone();
+                loom::thread::spawn(move || slots.head.swap(offsets[1], Ordering::AcqRel))
+            };
+            let mine = slots.head.swap(offsets[2], Ordering::AcqRel);
+            let theirs = other.join().unwrap();
+            let last = slots.head.load(Ordering::Acquire);
+
+            let mut seen = [mine, theirs, last];
+            seen.sort();
+            let mut expected = offsets;
+            expected.sort();
+            assert_eq!(seen, expected);
+        });
+    }
+}
+
 #[cfg(feature = "nightly")]
 mod nightly {
     use super::*;
@@ -204,7 +1721,7 @@ mod nightly {
 
     #[test]
     fn check_trait_object_simple() {
-        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| unsafe {
+        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| {
             TraitObject::from_mut(x as &mut dyn std::fmt::Debug)
         });
 
@@ -219,7 +1736,7 @@ mod nightly {
 
     #[test]
     fn check_trait_object_after_move() {
-        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| unsafe {
+        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| {
             TraitObject::from_mut(x as &mut dyn std::fmt::Debug)
         });
 
@@ -250,7 +1767,7 @@ mod nightly {
     #[test]
     #[cfg(feature = "std")]
     fn check_trait_object_after_move_heap() {
-        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| unsafe {
+        let mut s = SelfRefTest::new(TestStruct { value: 42 }, |x| {
             TraitObject::from_mut(x as &mut dyn std::fmt::Debug)
         });
 
@@ -272,4 +1789,40 @@ mod nightly {
             assert!(debug_str.contains("42"));
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn check_trait_object_cells() {
+        type DebugCell<T> = SelfRefCell<T, i8, TraitObject<dyn std::fmt::Debug>>;
+
+        fn debug_cell<T: std::fmt::Debug + 'static>(value: T) -> Box<DebugCell<T>> {
+            Box::new(
+                SelfRefCell::new_with(value, |x| {
+                    TraitObject::from_mut(x as &mut dyn std::fmt::Debug)
+                })
+                .unwrap(),
+            )
+        }
+
+        let cells: Vec<Box<DebugCell<dyn std::fmt::Debug>>> =
+            vec![debug_cell(TestStruct { value: 42 }), debug_cell("text")];
+        let cells = block_opt(cells);
+
+        let rendered: Vec<String> = cells
+            .iter()
+            .map(|cell| format!("{:?}", cell.get().as_ref()))
+            .collect();
+        assert_eq!(rendered, ["TestStruct { value: 42 }", "\"text\""]);
+    }
+
+    #[test]
+    fn trait_object_derefs() {
+        use std::any::Any;
+
+        let mut value = TestStruct { value: 42 };
+        let wrapped = TraitObject::from_mut(&mut value as &mut dyn Any);
+        wrapped.downcast_mut::<TestStruct>().unwrap().value += 1;
+        assert!(wrapped.downcast_ref::<u32>().is_none());
+        assert_eq!(value.value, 43);
+    }
 }